@@ -0,0 +1,17 @@
+pub mod blunders;
+pub mod card_value;
+pub mod distribution;
+pub mod exploitability;
+pub mod meta_game;
+pub mod plus_minus;
+pub mod probability;
+pub mod rule_variants;
+pub mod score_awareness;
+pub mod seat_order;
+pub mod strategy_diff;
+pub mod what_if;
+
+/// the standard error of a share estimated from the number of samples
+pub fn standard_error(share: f64, samples: usize) -> f64 {
+    (share * (1.0 - share) / samples.max(1) as f64).sqrt()
+}
@@ -0,0 +1,159 @@
+use super::what_if::{describe, evaluate, options, same_decision, Horizon};
+use crate::{recording::DecisionRecord, MyRng, Player};
+
+/// a recorded decision compared with the best option found by rollouts
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    /// the index of the record
+    pub index: usize,
+    pub player: usize,
+    pub turn_number: usize,
+    /// the recorded decision and the best option in words
+    pub chosen: String,
+    pub best: String,
+    /// how much the recorded decision is estimated to lose against the best option
+    /// in the unit of the horizon
+    pub loss: f64,
+    pub blunder: bool,
+}
+
+/// how well a player decided over the records
+#[derive(Debug, Clone, Default)]
+pub struct Accuracy {
+    pub decisions: usize,
+    pub blunders: usize,
+    pub total_loss: f64,
+}
+
+impl Accuracy {
+    /// the share of decisions which were no blunder
+    pub fn accuracy(&self) -> f64 {
+        1.0 - self.blunders as f64 / self.decisions.max(1) as f64
+    }
+
+    pub fn mean_loss(&self) -> f64 {
+        self.total_loss / self.decisions.max(1) as f64
+    }
+}
+
+/// the annotated decisions and the accuracy by seat
+#[derive(Debug, Clone)]
+pub struct Review {
+    pub annotations: Vec<Annotation>,
+    pub accuracy: Vec<Accuracy>,
+    /// the indices of the records whose decision is none of the legal options
+    pub unmatched: Vec<usize>,
+}
+
+impl Review {
+    pub fn blunders(&self) -> impl Iterator<Item = &Annotation> {
+        self.annotations
+            .iter()
+            .filter(|annotation| annotation.blunder)
+    }
+}
+
+/// replays every recorded decision and flags those losing more than the threshold
+///
+/// decisions without an alternative are counted as correct without rollouts.
+/// a decision matching no legal option can not be judged and is listed as unmatched
+pub fn annotate(
+    records: &[DecisionRecord],
+    policy: &mut dyn Player,
+    horizon: Horizon,
+    samples: usize,
+    threshold: f64,
+    rng: &mut MyRng,
+) -> Review {
+    let mut annotations = Vec::new();
+    let mut accuracy: Vec<Accuracy> = Vec::new();
+    let mut unmatched = Vec::new();
+    for (index, record) in records.iter().enumerate() {
+        let player = record.position.player();
+        if accuracy.len() <= player {
+            accuracy.resize(player + 1, Accuracy::default());
+        }
+        let (state, turn) = record.position.restore();
+        let legal = options(&turn);
+        if !legal
+            .iter()
+            .any(|option| same_decision(&turn, option, &record.decision))
+        {
+            unmatched.push(index);
+            continue;
+        }
+        let (best, loss) = if legal.len() == 1 {
+            (describe(&legal[0], &turn), 0.0)
+        } else {
+            let values = evaluate(&state, &turn, policy, horizon, samples, rng);
+            let chosen = values
+                .iter()
+                .find(|option| same_decision(&turn, &option.decision, &record.decision))
+                .expect("the decision is one of the legal options");
+            (values[0].describe(&turn), values[0].value - chosen.value)
+        };
+        let blunder = loss > threshold;
+        let player_accuracy = &mut accuracy[player];
+        player_accuracy.decisions += 1;
+        player_accuracy.blunders += blunder as usize;
+        player_accuracy.total_loss += loss;
+        annotations.push(Annotation {
+            index,
+            player,
+            turn_number: record.position.turn_number,
+            chosen: describe(&record.decision, &turn),
+            best,
+            loss,
+            blunder,
+        });
+    }
+    Review {
+        annotations,
+        accuracy,
+        unmatched,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{analysis::what_if::tests::last_die, recording::Decision, Move, ThresholdPlayer};
+
+    #[test]
+    fn writing_on_a_tutto_is_a_blunder() {
+        let position = last_die().position().unwrap();
+        let (_, turn) = position.restore();
+        let write = Move {
+            takes: turn.categorize_roll(),
+            write: true,
+        };
+        let records = [
+            DecisionRecord {
+                position: position.clone(),
+                decision: Decision::Move(write),
+            },
+            DecisionRecord {
+                position,
+                decision: Decision::Card(true),
+            },
+        ];
+        let review = annotate(
+            &records,
+            &mut ThresholdPlayer::default(),
+            Horizon::Turn,
+            20,
+            50.0,
+            &mut MyRng::seed_from_u64(0),
+        );
+        assert_eq!(review.unmatched, [1]);
+        assert_eq!(review.annotations.len(), 1);
+        let annotation = &review.annotations[0];
+        assert_eq!(annotation.chosen, "take [Single5] and write");
+        assert_eq!(annotation.best, "take [Single5] and roll on");
+        assert_eq!(annotation.loss, 300.0);
+        assert!(annotation.blunder);
+        assert_eq!(review.accuracy[0].accuracy(), 0.0);
+    }
+}
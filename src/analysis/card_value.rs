@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use rand::seq::SliceRandom;
+
+use crate::{deck::get_official_cards, Card, Card::*, Deck, GameState, MyRng, Player, Turn};
+
+/// what a single card is worth to a player
+#[derive(Debug, Clone)]
+pub struct CardValue {
+    pub card: Card,
+    pub samples: usize,
+    pub expected_points: f64,
+    /// the probability of at least one tutto
+    pub tutto: f64,
+    /// the probability that the card ends with an invalid roll
+    pub bust: f64,
+    /// the points written with their probability, ascending by points
+    pub distribution: Vec<(i32, f64)>,
+}
+
+/// every kind of card a turn can be played on, Flush included
+pub fn playable_cards() -> Vec<Card> {
+    let mut cards = get_official_cards();
+    cards.push(Flush);
+    let mut playable = Vec::new();
+    for card in cards {
+        if card != Stop && !playable.contains(&card) {
+            playable.push(card);
+        }
+    }
+    playable
+}
+
+/// plays the card in isolation at the start of a two player game
+///
+/// the turn ends with the card, a tutto is not followed by a new card
+/// and the points of a Clover or a PlusMinus tutto are the points of the card alone
+pub fn card_value(
+    player: &mut dyn Player,
+    card: Card,
+    samples: usize,
+    rng: &mut MyRng,
+) -> CardValue {
+    let mut state = GameState::new(2, get_official_cards(), rng);
+    let mut unseen = get_official_cards();
+    unseen.shuffle(rng);
+    state.deck = Deck::from_piles(unseen, vec![card]);
+    player.on_game_start(0, 2);
+
+    let mut counts: BTreeMap<i32, usize> = BTreeMap::new();
+    let (mut tuttos, mut busts) = (0, 0);
+    for _ in 0..samples {
+        let (points, tutto, bust) = play_card(player, &state, card, rng);
+        *counts.entry(points).or_default() += 1;
+        tuttos += tutto as usize;
+        busts += bust as usize;
+    }
+    let n = samples.max(1) as f64;
+    CardValue {
+        card,
+        samples,
+        expected_points: counts
+            .iter()
+            .map(|(p, c)| *p as f64 * *c as f64)
+            .sum::<f64>()
+            / n,
+        tutto: tuttos as f64 / n,
+        bust: busts as f64 / n,
+        distribution: counts.into_iter().map(|(p, c)| (p, c as f64 / n)).collect(),
+    }
+}
+
+/// the value of every playable card
+pub fn report(player: &mut dyn Player, samples: usize, rng: &mut MyRng) -> Vec<CardValue> {
+    playable_cards()
+        .into_iter()
+        .map(|card| card_value(player, card, samples, rng))
+        .collect()
+}
+
+/// returns the points written, whether a tutto was reached and whether the card busted
+/// mirrors the moves of GameState but stops after the card
+fn play_card(
+    player: &mut dyn Player,
+    state: &GameState,
+    card: Card,
+    rng: &mut MyRng,
+) -> (i32, bool, bool) {
+    let mut turn = Turn::new();
+    turn.new_card(card);
+    let mut tutto = false;
+    loop {
+        turn.roll_dice(rng);
+        if !turn.contains_valid_dice() {
+            turn.set_failed();
+            return (turn.previous_cards_total, tutto, true);
+        }
+        let this_move = player.make_move(state, &turn, rng);
+        tutto |= turn.dice_left_after(&turn.dice_of(&this_move.takes)) == 0;
+        turn.take_dice(this_move.takes);
+        if this_move.write && card != Clover {
+            turn.write_points();
+            return (turn.previous_cards_total, tutto, false);
+        }
+        if turn.is_tutto() {
+            turn.finish_card(state.rules());
+            return (turn.previous_cards_total, true, false);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::{fmt::Write, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{tournament::play_observed, Card, MyRng, Player};
+
+/// the widths of the bins of the histograms
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Bins {
+    pub points: i32,
+    pub turns: i32,
+}
+
+impl Default for Bins {
+    fn default() -> Self {
+        Self {
+            points: 100,
+            turns: 10,
+        }
+    }
+}
+
+/// the values from lower up to but excluding upper
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bin {
+    pub lower: i32,
+    pub upper: i32,
+    pub count: usize,
+}
+
+/// counts values in bins of equal width, empty bins are left out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Histogram {
+    pub width: i32,
+    pub samples: usize,
+    pub sum: i64,
+    /// ascending by lower
+    pub bins: Vec<Bin>,
+}
+
+impl Histogram {
+    pub fn new(width: i32) -> Self {
+        assert!(width > 0, "the width of a bin has to be positive");
+        Self {
+            width,
+            samples: 0,
+            sum: 0,
+            bins: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, value: i32) {
+        let lower = value.div_euclid(self.width) * self.width;
+        match self.bins.binary_search_by_key(&lower, |bin| bin.lower) {
+            Ok(idx) => self.bins[idx].count += 1,
+            Err(idx) => self.bins.insert(
+                idx,
+                Bin {
+                    lower,
+                    upper: lower + self.width,
+                    count: 1,
+                },
+            ),
+        }
+        self.samples += 1;
+        self.sum += value as i64;
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum as f64 / self.samples.max(1) as f64
+    }
+
+    /// the share of the samples in the bin
+    pub fn share(&self, bin: &Bin) -> f64 {
+        bin.count as f64 / self.samples.max(1) as f64
+    }
+
+    /// the upper bound of the first bin reaching the share q of the samples
+    pub fn quantile(&self, q: f64) -> Option<i32> {
+        let mut seen = 0;
+        self.bins.iter().find_map(|bin| {
+            seen += bin.count;
+            (seen as f64 >= q * self.samples as f64).then_some(bin.upper)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardHistogram {
+    pub card: Card,
+    pub histogram: Histogram,
+}
+
+/// the points of one player
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreDistribution {
+    /// the points written per turn, 0 for a failed turn
+    pub turn: Histogram,
+    /// the points logged per card played, in order of first appearance
+    /// cards before an invalid roll keep their points even though the turn loses them
+    pub cards: Vec<CardHistogram>,
+}
+
+impl ScoreDistribution {
+    fn new(bins: Bins) -> Self {
+        Self {
+            turn: Histogram::new(bins.points),
+            cards: Vec::new(),
+        }
+    }
+
+    fn card(&mut self, card: Card) -> &mut Histogram {
+        let idx = match self.cards.iter().position(|c| c.card == card) {
+            Some(idx) => idx,
+            None => {
+                self.cards.push(CardHistogram {
+                    card,
+                    histogram: Histogram::new(self.turn.width),
+                });
+                self.cards.len() - 1
+            }
+        };
+        &mut self.cards[idx].histogram
+    }
+}
+
+/// the distributions of a number of simulated games
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Distributions {
+    pub bins: Bins,
+    pub games: usize,
+    /// in the order of the players passed
+    pub players: Vec<ScoreDistribution>,
+    /// the turns of all players until the game ended
+    pub game_length: Histogram,
+}
+
+impl Distributions {
+    /// one row per bin: player, histogram, lower, upper, count, share
+    /// the player of the game length is left empty
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("player,histogram,lower,upper,count,share\n");
+        let mut rows = |player: String, name: String, histogram: &Histogram| {
+            for bin in &histogram.bins {
+                writeln!(
+                    csv,
+                    "{player},{name},{},{},{},{}",
+                    bin.lower,
+                    bin.upper,
+                    bin.count,
+                    histogram.share(bin)
+                )
+                .unwrap();
+            }
+        };
+        for (idx, player) in self.players.iter().enumerate() {
+            rows(idx.to_string(), "turn".into(), &player.turn);
+            for card in &player.cards {
+                rows(idx.to_string(), card.card.to_string(), &card.histogram);
+            }
+        }
+        rows(String::new(), "game_length".into(), &self.game_length);
+        csv
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn save_csv(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_csv())?;
+        Ok(())
+    }
+
+    pub fn save_json(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+/// plays the games rotating the seats after every game like win_rates
+pub fn collect(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    games: usize,
+    bins: Bins,
+    rng: &mut MyRng,
+) -> Distributions {
+    let n = players.len();
+    let mut distributions = Distributions {
+        bins,
+        games,
+        players: vec![ScoreDistribution::new(bins); n],
+        game_length: Histogram::new(bins.turns),
+    };
+    for game in 0..games {
+        let result = play_observed(players, cards, rng, |_, summary| {
+            let player = &mut distributions.players[(summary.player + game) % n];
+            player.turn.add(summary.points);
+            for log in &summary.cards {
+                player.card(log.card).add(log.points);
+            }
+        });
+        distributions.game_length.add(result.turns as i32);
+        players.rotate_left(1);
+    }
+    players.rotate_right(games % n.max(1));
+    distributions
+}
@@ -0,0 +1,124 @@
+use std::{
+    hash::Hasher,
+    path::{Path, PathBuf},
+};
+
+use rand_seeder::SipHasher;
+
+use crate::{
+    players::{Player, PlayerSpec, ThresholdParams, ThresholdPlayer},
+    tournament::win_rates,
+    training::evolution::{evolve, EvolutionConfig},
+    MyRng,
+};
+
+/// how much a fixed opponent loses to the strongest ThresholdParams found against it
+///
+/// the search only covers ThresholdParams, so the exploitability is a lower bound
+#[derive(Debug, Clone)]
+pub struct Exploitability {
+    pub players_per_game: usize,
+    /// the games of every evaluation after the search
+    pub games: usize,
+    /// the win rate of the opponent in a game against copies of itself
+    pub self_play: f64,
+    /// the win rate of the default ThresholdParams against the opponent
+    pub default_rate: f64,
+    pub best_response: ThresholdParams,
+    /// the win rate of the best response against the opponent on fresh games
+    /// never below default_rate
+    pub best_response_rate: f64,
+    /// where the search was checkpointed
+    pub dir: PathBuf,
+}
+
+impl Exploitability {
+    /// how much more the best response wins than the opponent against itself
+    pub fn exploitability(&self) -> f64 {
+        self.best_response_rate - self.self_play
+    }
+}
+
+/// searches the best response by evolving ThresholdParams against the opponent
+/// the search is checkpointed like evolve in the search_dir of the opponent
+/// and runs before the evaluations, so a failing search fails fast
+pub fn exploitability(
+    opponent: &PlayerSpec,
+    players_per_game: usize,
+    config: &EvolutionConfig,
+    games: usize,
+    dir: &Path,
+    rng: &mut MyRng,
+) -> anyhow::Result<Exploitability> {
+    assert!(players_per_game >= 2, "a game needs at least two players");
+    config.check()?;
+    let dir = search_dir(dir, opponent, players_per_game)?;
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("opponent.ron"), ron::to_string(opponent)?)?;
+    let mut opponents = (1..players_per_game)
+        .map(|_| opponent.build())
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut opponents: Vec<&mut dyn Player> =
+        opponents.iter_mut().map(|p| p as &mut dyn Player).collect();
+
+    let mut best_response = evolve(config, &mut opponents, &dir, rng)?;
+    let mut copy = opponent.build()?;
+    let self_play = win_rate(copy.as_mut(), &mut opponents, config, games, rng);
+    let default_rate = win_rate(
+        &mut ThresholdPlayer::default(),
+        &mut opponents,
+        config,
+        games,
+        rng,
+    );
+    let mut best_response_rate = win_rate(
+        &mut ThresholdPlayer::new(best_response.clone()),
+        &mut opponents,
+        config,
+        games,
+        rng,
+    );
+    // a short search can overfit its games and end up below where it started
+    if default_rate > best_response_rate {
+        best_response = ThresholdParams::default();
+        best_response_rate = default_rate;
+    }
+    Ok(Exploitability {
+        players_per_game,
+        games,
+        self_play,
+        default_rate,
+        best_response,
+        best_response_rate,
+        dir,
+    })
+}
+
+/// the subdirectory of dir for the search against the opponent
+/// named after a stable hash of the spec, so searches against different opponents
+/// never resume each other. the spec is kept next to the checkpoint in opponent.ron
+pub fn search_dir(
+    dir: &Path,
+    opponent: &PlayerSpec,
+    players_per_game: usize,
+) -> anyhow::Result<PathBuf> {
+    let mut hasher = SipHasher::new();
+    hasher.write(ron::to_string(opponent)?.as_bytes());
+    Ok(dir.join(format!(
+        "{:016x}_{players_per_game}_players",
+        hasher.finish()
+    )))
+}
+
+/// the win rate of the candidate in a game with the opponents
+fn win_rate(
+    candidate: &mut dyn Player,
+    opponents: &mut [&mut dyn Player],
+    config: &EvolutionConfig,
+    games: usize,
+    rng: &mut MyRng,
+) -> f64 {
+    let mut players: Vec<&mut dyn Player> = vec![candidate];
+    players.extend(opponents.iter_mut().map(|p| &mut **p as &mut dyn Player));
+    win_rates(&mut players, &config.cards, games, rng)[0]
+}
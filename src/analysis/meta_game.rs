@@ -0,0 +1,113 @@
+use crate::{
+    players::{Player, PlayerSpec},
+    tournament::win_rates,
+    Card, MyRng,
+};
+
+/// the win rates of every strategy against a field of every other strategy
+#[derive(Debug, Clone)]
+pub struct PayoffMatrix {
+    pub players_per_game: usize,
+    pub games: usize,
+    /// `payoff[i][j]` is the share of games strategy i won
+    /// against players_per_game - 1 players of strategy j
+    pub payoff: Vec<Vec<f64>>,
+}
+
+/// plays every strategy against every other strategy with the seats rotated
+/// the diagonal is the fair share and is not simulated
+pub fn payoff_matrix(
+    specs: &[PlayerSpec],
+    players_per_game: usize,
+    cards: &[Card],
+    games: usize,
+    rng: &mut MyRng,
+) -> anyhow::Result<PayoffMatrix> {
+    assert!(players_per_game >= 2, "a game needs at least two players");
+    let n = specs.len();
+    let mut payoff = vec![vec![1.0 / players_per_game as f64; n]; n];
+    for (i, row) in payoff.iter_mut().enumerate() {
+        for (j, cell) in row.iter_mut().enumerate() {
+            if i == j {
+                continue;
+            }
+            let mut field = vec![specs[i].build()?];
+            for _ in 1..players_per_game {
+                field.push(specs[j].build()?);
+            }
+            let mut field: Vec<&mut dyn Player> =
+                field.iter_mut().map(|p| p as &mut dyn Player).collect();
+            *cell = win_rates(&mut field, cards, games, rng)[0];
+        }
+    }
+    Ok(PayoffMatrix {
+        players_per_game,
+        games,
+        payoff,
+    })
+}
+
+/// a mixture of the strategies no strategy gains much against
+#[derive(Debug, Clone)]
+pub struct Equilibrium {
+    pub mixture: Vec<f64>,
+    /// the win rate of the mixture against itself
+    pub value: f64,
+    /// how much the best strategy wins more than the mixture against the mixture
+    /// 0 for an exact equilibrium
+    pub exploitability: f64,
+}
+
+impl PayoffMatrix {
+    /// the win rate of every strategy against a field playing the mixture
+    /// with more than two players the field is assumed to pick one strategy for all seats
+    pub fn against(&self, mixture: &[f64]) -> Vec<f64> {
+        self.payoff
+            .iter()
+            .map(|row| row.iter().zip(mixture).map(|(a, x)| a * x).sum())
+            .collect()
+    }
+
+    /// the symmetric equilibrium approximated by fictitious play
+    /// every iteration adds the best response to the mixture of all earlier ones
+    pub fn equilibrium(&self, iterations: usize) -> Equilibrium {
+        let n = self.payoff.len();
+        let mut counts = vec![0; n];
+        let mut mixture = vec![1.0 / n as f64; n];
+        for iteration in 1..=iterations {
+            counts[best_response(&self.against(&mixture))] += 1;
+            mixture = counts
+                .iter()
+                .map(|c| *c as f64 / iteration as f64)
+                .collect();
+        }
+        let payoffs = self.against(&mixture);
+        let value: f64 = payoffs.iter().zip(&mixture).map(|(a, x)| a * x).sum();
+        Equilibrium {
+            exploitability: payoffs[best_response(&payoffs)] - value,
+            value,
+            mixture,
+        }
+    }
+
+    /// the strategies doing worse than another one against every field
+    /// returned with the index of a dominating strategy
+    pub fn dominated(&self) -> Vec<(usize, usize)> {
+        let n = self.payoff.len();
+        (0..n)
+            .filter_map(|i| {
+                (0..n)
+                    .find(|k| *k != i && (0..n).all(|j| self.payoff[*k][j] > self.payoff[i][j]))
+                    .map(|k| (i, k))
+            })
+            .collect()
+    }
+}
+
+/// the first index wins ties
+fn best_response(payoffs: &[f64]) -> usize {
+    (0..payoffs.len())
+        .rev()
+        .max_by(|a, b| payoffs[*a].total_cmp(&payoffs[*b]))
+        .unwrap()
+}
@@ -0,0 +1,78 @@
+use crate::{players::Player, tournament::play_observed, Card, Card::*, MyRng, TurnEnd};
+
+/// how the PlusMinus cards shaped the games of a pool of players
+#[derive(Debug, Default)]
+pub struct PlusMinusReport {
+    pub games: usize,
+    /// the games in which at least one PlusMinus was achieved
+    pub games_with_plus_minus: usize,
+    /// the games which the same turns without the PlusMinus points would have given another winner
+    pub changed_winner: usize,
+    /// the following are indexed by player
+    pub wins: Vec<usize>,
+    /// the PlusMinus cards finished with a tutto
+    pub achieved: Vec<usize>,
+    /// how often a PlusMinus took points from the player
+    pub stolen_from: Vec<usize>,
+}
+
+/// plays the games rotating the seats and tracks every PlusMinus
+///
+/// the counterfactual winner is found by replaying the score changes of the same turns
+/// without the points of a PlusMinus tutto and without the stolen points
+pub fn report(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    games: usize,
+    rng: &mut MyRng,
+) -> PlusMinusReport {
+    let n = players.len();
+    let mut report = PlusMinusReport {
+        games,
+        wins: vec![0; n],
+        achieved: vec![0; n],
+        stolen_from: vec![0; n],
+        ..Default::default()
+    };
+    for game in 0..games {
+        let player_of = |seat: usize| (seat + game) % n;
+        let mut before = vec![0; n];
+        let mut without = vec![0; n];
+        let mut first_without = None;
+        let mut any_plus_minus = false;
+        let result = play_observed(players, cards, rng, |state, summary| {
+            let seat = summary.player;
+            for victim in &summary.minus {
+                report.stolen_from[player_of(*victim)] += 1;
+            }
+            let achieved =
+                !summary.minus.is_empty() || summary.end == (TurnEnd::Finished { card: PlusMinus });
+            let mut gained = summary.scores[seat] - before[seat];
+            if achieved {
+                any_plus_minus = true;
+                report.achieved[player_of(seat)] += 1;
+                gained -= state.rules().plus_minus;
+            }
+            without[seat] += gained;
+            if first_without.is_none() && without[seat] >= state.rules().point_goal {
+                first_without = Some(seat);
+            }
+            before.clone_from(&summary.scores);
+        });
+        players.rotate_left(1);
+
+        let winner = result.winner;
+        let winner_without = first_without.or_else(|| (0..n).max_by_key(|seat| without[*seat]));
+        if let Some(seat) = winner {
+            report.wins[player_of(seat)] += 1;
+        }
+        if any_plus_minus {
+            report.games_with_plus_minus += 1;
+        }
+        if winner != winner_without {
+            report.changed_winner += 1;
+        }
+    }
+    players.rotate_right(games % n.max(1));
+    report
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use crate::{players::take_selection::take_subsets, Card::*, TakenDice, Turn, NUMBER_OF_DICE};
+
+/// the outcomes of a roll of some dice on a normal card
+#[derive(Debug, Clone)]
+pub struct RollTable {
+    pub dice: usize,
+    /// the probability that the roll offers no take
+    pub bust: f64,
+    /// the probability of each set of offered dice, most likely first
+    pub patterns: Vec<(Vec<TakenDice>, f64)>,
+}
+
+/// the outcomes of a roll on a Flush after some numbers were set aside
+#[derive(Debug, Clone)]
+pub struct FlushRollTable {
+    /// the numbers already set aside
+    pub taken: usize,
+    pub dice: usize,
+    /// the probability that no die shows a new number
+    pub bust: f64,
+    /// the probability of the number of new numbers offered, indexed by that number
+    pub new_numbers: Vec<f64>,
+}
+
+/// which of the offered dice are set aside on the way to a tutto
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TakePolicy {
+    /// everything offered
+    All,
+    /// a single take, preferring the fewest dice
+    One,
+    /// whatever maximizes the probability of the tutto
+    Best,
+}
+
+impl TakePolicy {
+    pub const ALL: [TakePolicy; 3] = [TakePolicy::All, TakePolicy::One, TakePolicy::Best];
+}
+
+/// every roll of the dice in lexicographic order, each has probability 6^-dice
+fn rolls(dice: usize) -> impl Iterator<Item = Vec<u8>> {
+    (0..6_usize.pow(dice as u32)).map(move |mut index| {
+        (0..dice)
+            .map(|_| {
+                let value = (index % 6) as u8 + 1;
+                index /= 6;
+                value
+            })
+            .collect()
+    })
+}
+
+/// a turn on a normal card with the roll and the other dice set aside
+fn normal_turn(roll: Vec<u8>) -> Turn {
+    let mut turn = Turn::new();
+    turn.new_card(Bonus(200));
+    turn.taken_dice = vec![TakenDice::Single5; NUMBER_OF_DICE - roll.len()];
+    turn.roll = roll;
+    turn
+}
+
+/// a turn on a Flush with the numbers 1 to taken set aside
+fn flush_turn(taken: usize, roll: Vec<u8>) -> Turn {
+    let mut turn = Turn::new();
+    turn.new_card(Flush);
+    turn.taken_dice = (1..=taken as u8).map(TakenDice::SingleFlush).collect();
+    turn.roll = roll;
+    turn
+}
+
+/// enumerates every roll of the dice on a normal card
+pub fn normal_roll(dice: usize) -> RollTable {
+    let total = 6_f64.powi(dice as i32);
+    let mut counts: HashMap<Vec<TakenDice>, usize> = HashMap::new();
+    for roll in rolls(dice) {
+        let turn = normal_turn(roll);
+        *counts
+            .entry(turn.dice_of(&turn.categorize_roll()))
+            .or_default() += 1;
+    }
+    let bust = counts.remove(&Vec::new()).unwrap_or(0) as f64 / total;
+    let mut patterns: Vec<_> = counts
+        .into_iter()
+        .map(|(pattern, n)| (pattern, n as f64 / total))
+        .collect();
+    patterns.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    RollTable {
+        dice,
+        bust,
+        patterns,
+    }
+}
+
+/// enumerates every roll of the dice left on a Flush
+pub fn flush_roll(taken: usize) -> FlushRollTable {
+    let dice = NUMBER_OF_DICE - taken;
+    let total = 6_f64.powi(dice as i32);
+    let mut new_numbers = vec![0.0; dice + 1];
+    for roll in rolls(dice) {
+        new_numbers[flush_turn(taken, roll).categorize_flush().len()] += 1.0 / total;
+    }
+    FlushRollTable {
+        taken,
+        dice,
+        bust: new_numbers[0],
+        new_numbers,
+    }
+}
+
+/// the probability of reaching a tutto on a normal card, indexed by the dice left
+/// index 0 is the tutto itself
+pub fn tutto_chance(policy: TakePolicy) -> [f64; NUMBER_OF_DICE + 1] {
+    let mut chance = [0.0; NUMBER_OF_DICE + 1];
+    chance[0] = 1.0;
+    for dice in 1..=NUMBER_OF_DICE {
+        let total = 6_f64.powi(dice as i32);
+        for roll in rolls(dice) {
+            let turn = normal_turn(roll);
+            let taken = |takes: &[_]| {
+                turn.dice_of(takes)
+                    .iter()
+                    .map(TakenDice::number_of_dice)
+                    .sum::<usize>()
+            };
+            let offered = turn.categorize_roll();
+            if offered.is_empty() {
+                continue;
+            }
+            let value = match policy {
+                TakePolicy::All => chance[dice - taken(&offered)],
+                TakePolicy::One => {
+                    let fewest = offered.iter().map(|take| taken(&[*take])).min().unwrap();
+                    chance[dice - fewest]
+                }
+                TakePolicy::Best => take_subsets(&turn)
+                    .iter()
+                    .map(|takes| chance[dice - taken(takes)])
+                    .fold(0.0, f64::max),
+            };
+            chance[dice] += value / total;
+        }
+    }
+    chance
+}
+
+/// the probability of reaching a tutto on a Flush, indexed by the dice left
+/// index 0 is the tutto itself
+pub fn flush_tutto_chance(policy: TakePolicy) -> [f64; NUMBER_OF_DICE + 1] {
+    let mut chance = [0.0; NUMBER_OF_DICE + 1];
+    chance[0] = 1.0;
+    for dice in 1..=NUMBER_OF_DICE {
+        let table = flush_roll(NUMBER_OF_DICE - dice);
+        chance[dice] = table
+            .new_numbers
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(new, p)| {
+                let value = match policy {
+                    TakePolicy::All => chance[dice - new],
+                    TakePolicy::One => chance[dice - 1],
+                    TakePolicy::Best => (1..=new).map(|j| chance[dice - j]).fold(0.0, f64::max),
+                };
+                p * value
+            })
+            .sum();
+    }
+    chance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-12, "{a} != {b}");
+    }
+
+    #[test]
+    fn normal_busts() {
+        assert_close(normal_roll(1).bust, 4.0 / 6.0);
+        assert_close(normal_roll(2).bust, 16.0 / 36.0);
+        // no 1, no 5 and no triple of 2, 3, 4 or 6
+        assert_close(normal_roll(3).bust, 60.0 / 216.0);
+        assert_close(normal_roll(6).bust, 1440.0 / 46656.0);
+        for dice in 1..=NUMBER_OF_DICE {
+            let table = normal_roll(dice);
+            let total: f64 = table.patterns.iter().map(|(_, p)| p).sum();
+            assert_close(table.bust + total, 1.0);
+        }
+    }
+
+    #[test]
+    fn flush_busts() {
+        assert_close(flush_roll(0).bust, 0.0);
+        assert_close(flush_roll(5).bust, 5.0 / 6.0);
+        assert_close(flush_roll(4).bust, 16.0 / 36.0);
+        assert_close(flush_roll(4).new_numbers[2], 2.0 / 36.0);
+    }
+
+    #[test]
+    fn tutto_chances() {
+        let [all, one, best] = TakePolicy::ALL.map(tutto_chance);
+        for (chance, policy) in [all, one, best].into_iter().zip(TakePolicy::ALL) {
+            assert_close(chance[0], 1.0);
+            assert_close(chance[1], 1.0 / 3.0);
+            assert_close(flush_tutto_chance(policy)[1], 1.0 / 6.0);
+        }
+        // with two dice only a 1 or 5 on both dice is a tutto at once
+        assert_close(all[2], 4.0 / 36.0 + 16.0 / 36.0 / 3.0);
+        // two new numbers at once or one new number and then the last
+        assert_close(
+            flush_tutto_chance(TakePolicy::All)[2],
+            2.0 / 36.0 + 18.0 / 36.0 / 6.0,
+        );
+        for chance in [all, one] {
+            assert!((1..=NUMBER_OF_DICE).all(|dice| best[dice] >= chance[dice] - 1e-12));
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    deck::get_official_cards,
+    make_rng,
+    players::{Player, PlayerSpec},
+    tournament::{default_games, play_with_rules},
+    Card, MyRng, Rules,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedDeck {
+    pub name: String,
+    pub cards: Vec<Card>,
+}
+
+/// a grid of rule variants described in a ron file
+/// every axis left empty keeps the value of the official rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepConfig {
+    pub players: Vec<PlayerSpec>,
+    /// the games per variant
+    #[serde(default = "default_games")]
+    pub games: usize,
+    #[serde(default)]
+    pub seed: Option<String>,
+    #[serde(default)]
+    pub decks: Vec<NamedDeck>,
+    /// the number of Stop cards replacing those of the deck
+    #[serde(default)]
+    pub stops: Vec<usize>,
+    #[serde(default)]
+    pub point_goals: Vec<i32>,
+    #[serde(default)]
+    pub plus_minus: Vec<i32>,
+    #[serde(default)]
+    pub flush_rewards: Vec<i32>,
+    #[serde(default)]
+    pub finish_round: Vec<bool>,
+}
+
+/// one point of the grid
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub deck: String,
+    pub cards: Vec<Card>,
+    pub rules: Rules,
+}
+
+impl Variant {
+    pub fn name(&self) -> String {
+        let stops = self
+            .cards
+            .iter()
+            .filter(|card| **card == Card::Stop)
+            .count();
+        format!(
+            "deck={} stops={stops} goal={} plus_minus={} flush={} finish_round={}",
+            self.deck,
+            self.rules.point_goal,
+            self.rules.plus_minus,
+            self.rules.flush_reward,
+            self.rules.finish_round
+        )
+    }
+}
+
+/// how the strategy pool fared under a variant
+#[derive(Debug, Clone)]
+pub struct VariantReport {
+    pub variant: Variant,
+    pub games: usize,
+    /// the turns of all players until the game ended
+    pub mean_turns: f64,
+    pub turns_deviation: f64,
+    /// the points written per turn, 0 for a failed turn
+    pub mean_turn_points: f64,
+    pub turn_points_deviation: f64,
+    /// the share of games won from each seat
+    pub by_seat: Vec<f64>,
+    /// the share of games won by each player in the order of the config
+    pub by_player: Vec<f64>,
+    pub draws: f64,
+    /// the share of games ending in a tie for the highest score, left out of the wins
+    pub ties: f64,
+}
+
+impl VariantReport {
+    /// the win rate of the first seat over that of a fair seat
+    pub fn first_seat_advantage(&self) -> f64 {
+        self.by_seat[0] - (1.0 - self.draws - self.ties) / self.by_seat.len() as f64
+    }
+
+    /// the players from the best to the worst
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut ranking: Vec<usize> = (0..self.by_player.len()).collect();
+        ranking.sort_by(|a, b| self.by_player[*b].total_cmp(&self.by_player[*a]));
+        ranking
+    }
+}
+
+impl SweepConfig {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(ron::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// every combination of the axes, the first axes change slowest
+    pub fn variants(&self) -> Vec<Variant> {
+        let official = [NamedDeck {
+            name: "official".into(),
+            cards: get_official_cards(),
+        }];
+        let decks = if self.decks.is_empty() {
+            &official[..]
+        } else {
+            &self.decks
+        };
+        let default = Rules::default();
+        let stops = axis(&self.stops, None, Some);
+        let point_goals = axis(&self.point_goals, default.point_goal, |x| x);
+        let plus_minus = axis(&self.plus_minus, default.plus_minus, |x| x);
+        let flush_rewards = axis(&self.flush_rewards, default.flush_reward, |x| x);
+        let finish_round = axis(&self.finish_round, default.finish_round, |x| x);
+
+        let mut variants = Vec::new();
+        for deck in decks {
+            for stop in &stops {
+                let cards = match stop {
+                    Some(n) => with_stops(&deck.cards, *n),
+                    None => deck.cards.clone(),
+                };
+                for point_goal in &point_goals {
+                    for plus_minus in &plus_minus {
+                        for flush_reward in &flush_rewards {
+                            for finish_round in &finish_round {
+                                variants.push(Variant {
+                                    deck: deck.name.clone(),
+                                    cards: cards.clone(),
+                                    rules: Rules {
+                                        point_goal: *point_goal,
+                                        plus_minus: *plus_minus,
+                                        flush_reward: *flush_reward,
+                                        finish_round: *finish_round,
+                                    },
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        variants
+    }
+
+    /// builds the players and plays every variant
+    pub fn run(&self) -> anyhow::Result<Vec<VariantReport>> {
+        let mut players = self
+            .players
+            .iter()
+            .map(PlayerSpec::build)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let mut players: Vec<&mut dyn Player> =
+            players.iter_mut().map(|p| p as &mut dyn Player).collect();
+        let mut rng = make_rng(self.seed.as_deref());
+        Ok(self
+            .variants()
+            .into_iter()
+            .map(|variant| play_variant(&mut players, variant, self.games, &mut rng))
+            .collect())
+    }
+}
+
+fn axis<T: Copy, U>(values: &[T], default: U, wrap: impl Fn(T) -> U) -> Vec<U> {
+    if values.is_empty() {
+        vec![default]
+    } else {
+        values.iter().map(|x| wrap(*x)).collect()
+    }
+}
+
+/// the cards with all Stop cards replaced by n of them
+pub fn with_stops(cards: &[Card], n: usize) -> Vec<Card> {
+    let mut cards: Vec<Card> = cards
+        .iter()
+        .copied()
+        .filter(|card| *card != Card::Stop)
+        .collect();
+    cards.extend(std::iter::repeat_n(Card::Stop, n));
+    cards
+}
+
+/// plays the games rotating the seats after every game like win_rates
+pub fn play_variant(
+    players: &mut [&mut dyn Player],
+    variant: Variant,
+    games: usize,
+    rng: &mut MyRng,
+) -> VariantReport {
+    let n = players.len();
+    let mut by_seat = vec![0; n];
+    let mut by_player = vec![0; n];
+    let mut draws = 0;
+    let mut ties = 0;
+    let mut turns = Moments::default();
+    let mut turn_points = Moments::default();
+    for game in 0..games {
+        let result = play_with_rules(
+            players,
+            &variant.cards,
+            &variant.rules,
+            rng,
+            |_, summary| turn_points.add(summary.points as f64),
+        );
+        match result.winner {
+            Some(_) if result.tied() => ties += 1,
+            Some(seat) => {
+                by_seat[seat] += 1;
+                by_player[(seat + game) % n] += 1;
+            }
+            None => draws += 1,
+        }
+        turns.add(result.turns as f64);
+        players.rotate_left(1);
+    }
+    players.rotate_right(games % n.max(1));
+    let share = |wins: usize| wins as f64 / games.max(1) as f64;
+    VariantReport {
+        variant,
+        games,
+        mean_turns: turns.mean(),
+        turns_deviation: turns.deviation(),
+        mean_turn_points: turn_points.mean(),
+        turn_points_deviation: turn_points.deviation(),
+        by_seat: by_seat.into_iter().map(share).collect(),
+        by_player: by_player.into_iter().map(share).collect(),
+        draws: share(draws),
+        ties: share(ties),
+    }
+}
+
+#[derive(Default)]
+struct Moments {
+    n: usize,
+    sum: f64,
+    sum_of_squares: f64,
+}
+
+impl Moments {
+    fn add(&mut self, x: f64) {
+        self.n += 1;
+        self.sum += x;
+        self.sum_of_squares += x * x;
+    }
+
+    fn mean(&self) -> f64 {
+        self.sum / self.n.max(1) as f64
+    }
+
+    fn deviation(&self) -> f64 {
+        (self.sum_of_squares / self.n.max(1) as f64 - self.mean().powi(2))
+            .max(0.0)
+            .sqrt()
+    }
+}
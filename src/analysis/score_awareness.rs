@@ -0,0 +1,61 @@
+use super::standard_error;
+use crate::{
+    players::{Player, ScoreAwarePlayer, ScoreAwareness, ThresholdParams, ThresholdPlayer},
+    tournament::win_rates,
+    Card, MyRng,
+};
+
+/// the win rates of one score-aware player against score-blind copies of itself
+pub struct ScoreAwarenessReport {
+    pub players: usize,
+    pub games: usize,
+    /// the win rate of a score-blind player in the same seat, the control
+    pub blind: f64,
+    pub aware: f64,
+}
+
+impl ScoreAwarenessReport {
+    /// the win rate gained by reading the scoreboard
+    pub fn improvement(&self) -> f64 {
+        self.aware - self.blind
+    }
+
+    /// the standard error of the improvement
+    pub fn standard_error(&self) -> f64 {
+        (standard_error(self.aware, self.games).powi(2)
+            + standard_error(self.blind, self.games).powi(2))
+        .sqrt()
+    }
+}
+
+/// plays the same thresholds once with and once without awareness against blind opponents
+pub fn compare(
+    params: &ThresholdParams,
+    awareness: &ScoreAwareness,
+    players: usize,
+    games: usize,
+    cards: &[Card],
+    rng: &mut MyRng,
+) -> ScoreAwarenessReport {
+    let mut opponents: Vec<_> = (1..players)
+        .map(|_| ThresholdPlayer::new(params.clone()))
+        .collect();
+    let mut rate_of = |candidate: &mut dyn Player| {
+        let mut seats: Vec<&mut dyn Player> = vec![candidate];
+        for opponent in opponents.iter_mut() {
+            seats.push(opponent);
+        }
+        win_rates(&mut seats, cards, games, rng)[0]
+    };
+    let blind = rate_of(&mut ThresholdPlayer::new(params.clone()));
+    let aware = rate_of(&mut ScoreAwarePlayer::new(
+        params.clone(),
+        awareness.clone(),
+    ));
+    ScoreAwarenessReport {
+        players,
+        games,
+        blind,
+        aware,
+    }
+}
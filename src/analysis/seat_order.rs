@@ -0,0 +1,124 @@
+use super::standard_error;
+use crate::{tournament::play_with_rules, Card, MyRng, Player, Rules};
+
+/// the wins by seat over every order of the players
+#[derive(Debug, Clone)]
+pub struct SeatAdvantage {
+    pub rules: Rules,
+    /// the games played in total, the same number for every order
+    pub games: usize,
+    /// the share of games won from each seat
+    pub by_seat: Vec<f64>,
+    /// the share of games won by each player in the order passed
+    pub by_player: Vec<f64>,
+    /// the share of games nobody won within MAX_TURNS
+    pub draws: f64,
+    /// the share of games ending in a tie for the highest score
+    /// the engine gives these to the earlier seat, so they are left out of the wins
+    pub ties: f64,
+}
+
+impl SeatAdvantage {
+    pub fn standard_error(&self, seat: usize) -> f64 {
+        standard_error(self.by_seat[seat], self.games)
+    }
+
+    /// the win rate of the first seat over that of a fair seat
+    pub fn first_seat_advantage(&self) -> f64 {
+        self.by_seat[0] - (1.0 - self.draws - self.ties) / self.by_seat.len() as f64
+    }
+}
+
+/// plays the games in every order of the players
+pub fn seat_advantage(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    rules: &Rules,
+    games_per_order: usize,
+    rng: &mut MyRng,
+) -> SeatAdvantage {
+    let n = players.len();
+    let mut by_seat = vec![0; n];
+    let mut by_player = vec![0; n];
+    let mut draws = 0;
+    let mut ties = 0;
+    let mut games = 0;
+    for_each_order(players, |players, seats| {
+        for _ in 0..games_per_order {
+            let result = play_with_rules(players, cards, rules, rng, |_, _| ());
+            match result.winner {
+                Some(_) if result.tied() => ties += 1,
+                Some(seat) => {
+                    by_seat[seat] += 1;
+                    by_player[seats[seat]] += 1;
+                }
+                None => draws += 1,
+            }
+            games += 1;
+        }
+    });
+    let share = |wins: usize| wins as f64 / games.max(1) as f64;
+    SeatAdvantage {
+        rules: rules.clone(),
+        games,
+        by_seat: by_seat.into_iter().map(share).collect(),
+        by_player: by_player.into_iter().map(share).collect(),
+        draws: share(draws),
+        ties: share(ties),
+    }
+}
+
+/// the seat advantage with the official rules and with the round finished
+pub fn compare(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    games_per_order: usize,
+    rng: &mut MyRng,
+) -> [SeatAdvantage; 2] {
+    [false, true].map(|finish_round| {
+        seat_advantage(
+            players,
+            cards,
+            &Rules {
+                finish_round,
+                ..Rules::default()
+            },
+            games_per_order,
+            rng,
+        )
+    })
+}
+
+/// visits every order of the players by Heap's algorithm
+/// `seats[seat]` is the index the player in the seat was passed with
+/// the original order is restored afterwards
+fn for_each_order(
+    players: &mut [&mut dyn Player],
+    mut visit: impl FnMut(&mut [&mut dyn Player], &[usize]),
+) {
+    let n = players.len();
+    let mut seats: Vec<usize> = (0..n).collect();
+    let mut counters = vec![0; n];
+    visit(players, &seats);
+    let mut i = 1;
+    while i < n {
+        if counters[i] < i {
+            let j = if i % 2 == 0 { 0 } else { counters[i] };
+            players.swap(j, i);
+            seats.swap(j, i);
+            visit(players, &seats);
+            counters[i] += 1;
+            i = 1;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+    for seat in 0..n {
+        while seats[seat] != seat {
+            let other = seats[seat];
+            players.swap(seat, other);
+            seats.swap(seat, other);
+        }
+    }
+}
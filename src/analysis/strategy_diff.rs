@@ -0,0 +1,253 @@
+use rand::{Rng, SeedableRng};
+
+use super::what_if::{describe, evaluate, same_decision, Horizon};
+use crate::{
+    recording::{Decision, Position},
+    tournament::win_rates,
+    Card, GameResult, GameState, Move, MyRng, Player, Turn, TurnSummary,
+};
+
+/// a position where the two players decided differently
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+    pub position: Position,
+    pub a: Decision,
+    pub b: Decision,
+    /// the value of the decision of a minus that of b in the unit of the horizon
+    /// None if it was not estimated or a decision was not legal
+    pub value_difference: Option<f64>,
+}
+
+impl Disagreement {
+    /// both decisions in words
+    pub fn describe(&self) -> (String, String) {
+        let (_, turn) = self.position.restore();
+        (describe(&self.a, &turn), describe(&self.b, &turn))
+    }
+}
+
+/// how often the players disagree on one card
+#[derive(Debug, Clone)]
+pub struct CardDiff {
+    pub card: Card,
+    pub positions: usize,
+    pub disagreements: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct StrategyDiff {
+    pub positions: usize,
+    pub disagreements: Vec<Disagreement>,
+    /// in order of first appearance
+    pub by_card: Vec<CardDiff>,
+}
+
+impl StrategyDiff {
+    pub fn disagreement_rate(&self) -> f64 {
+        self.disagreements.len() as f64 / self.positions.max(1) as f64
+    }
+
+    /// the mean value difference of the estimated disagreements per position
+    /// positive if a decides better
+    pub fn mean_value_difference(&self) -> f64 {
+        self.disagreements
+            .iter()
+            .filter_map(|d| d.value_difference)
+            .sum::<f64>()
+            / self.positions.max(1) as f64
+    }
+}
+
+/// collects the positions of every decision of the wrapped player
+struct Collector<'a> {
+    player: &'a mut dyn Player,
+    positions: Vec<Position>,
+}
+
+impl Player for Collector<'_> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.positions.push(Position::capture(state, turn));
+        self.player.make_move(state, turn, rng)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        self.positions.push(Position::capture(state, last_turn));
+        self.player.card_strat(state, last_turn, rng)
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        self.player.on_game_start(seat, n_players)
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        self.player.on_turn_end(summary)
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.player.on_game_end(result)
+    }
+}
+
+/// the positions of every decision in the games, the seats rotated like win_rates
+pub fn sample_positions(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    games: usize,
+    rng: &mut MyRng,
+) -> Vec<Position> {
+    let mut collectors: Vec<_> = players
+        .iter_mut()
+        .map(|player| Collector {
+            player: &mut **player,
+            positions: Vec::new(),
+        })
+        .collect();
+    let mut wrapped: Vec<&mut dyn Player> = Vec::new();
+    for collector in collectors.iter_mut() {
+        wrapped.push(collector);
+    }
+    win_rates(&mut wrapped, cards, games, rng);
+    collectors
+        .into_iter()
+        .flat_map(|collector| collector.positions)
+        .collect()
+}
+
+/// the decision of the player in the position
+/// the player is told its seat first as if a game started
+fn decide(player: &mut dyn Player, position: &Position, rng: &mut MyRng) -> Decision {
+    let (state, turn) = position.restore();
+    player.on_game_start(position.player(), position.scores.len());
+    if turn.card_is_finished {
+        Decision::Card(player.card_strat(&state, &turn, rng))
+    } else {
+        Decision::Move(player.make_move(&state, &turn, rng))
+    }
+}
+
+/// asks both players for their decision in every position
+///
+/// both players see the same seed in a position. the value difference of every disagreement
+/// is estimated by rollouts of the policy unless samples is 0
+pub fn diff(
+    a: &mut dyn Player,
+    b: &mut dyn Player,
+    positions: &[Position],
+    policy: &mut dyn Player,
+    horizon: Horizon,
+    samples: usize,
+    rng: &mut MyRng,
+) -> StrategyDiff {
+    let mut disagreements = Vec::new();
+    let mut by_card: Vec<CardDiff> = Vec::new();
+    for position in positions {
+        let seed: u64 = rng.gen();
+        let decision_a = decide(a, position, &mut MyRng::seed_from_u64(seed));
+        let decision_b = decide(b, position, &mut MyRng::seed_from_u64(seed));
+        let (state, turn) = position.restore();
+        let differs = !same_decision(&turn, &decision_a, &decision_b);
+
+        let idx = match by_card.iter().position(|c| c.card == position.card) {
+            Some(idx) => idx,
+            None => {
+                by_card.push(CardDiff {
+                    card: position.card,
+                    positions: 0,
+                    disagreements: 0,
+                });
+                by_card.len() - 1
+            }
+        };
+        by_card[idx].positions += 1;
+        by_card[idx].disagreements += differs as usize;
+        if !differs {
+            continue;
+        }
+
+        let value_difference = if samples == 0 {
+            None
+        } else {
+            let values = evaluate(&state, &turn, policy, horizon, samples, rng);
+            let value_of = |decision: &Decision| {
+                values
+                    .iter()
+                    .find(|option| same_decision(&turn, &option.decision, decision))
+                    .map(|option| option.value)
+            };
+            value_of(&decision_a)
+                .zip(value_of(&decision_b))
+                .map(|(a, b)| a - b)
+        };
+        disagreements.push(Disagreement {
+            position: position.clone(),
+            a: decision_a,
+            b: decision_b,
+            value_difference,
+        });
+    }
+    StrategyDiff {
+        positions: positions.len(),
+        disagreements,
+        by_card,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{analysis::what_if::tests::last_die, NaivePlayer, ThresholdPlayer};
+
+    /// plays like NaivePlayer but writes whatever it takes, even on a tutto
+    struct AlwaysWrites;
+
+    impl Player for AlwaysWrites {
+        fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+            Move {
+                write: true,
+                ..NaivePlayer.make_move(state, turn, rng)
+            }
+        }
+
+        fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+            NaivePlayer.card_strat(state, last_turn, rng)
+        }
+    }
+
+    #[test]
+    fn writing_on_a_tutto_is_a_disagreement() {
+        let positions = [last_die().position().unwrap()];
+        let mut policy = ThresholdPlayer::default();
+        let rng = &mut MyRng::seed_from_u64(0);
+
+        let same = diff(
+            &mut NaivePlayer,
+            &mut NaivePlayer,
+            &positions,
+            &mut policy,
+            Horizon::Turn,
+            20,
+            rng,
+        );
+        assert!(same.disagreements.is_empty());
+
+        let report = diff(
+            &mut AlwaysWrites,
+            &mut NaivePlayer,
+            &positions,
+            &mut policy,
+            Horizon::Turn,
+            20,
+            rng,
+        );
+        assert_eq!(report.disagreement_rate(), 1.0);
+        assert_eq!(report.by_card[0].card, Card::Bonus(300));
+        assert_eq!(report.disagreements[0].value_difference, Some(-300.0));
+        assert_eq!(
+            report.disagreements[0].describe(),
+            (
+                "take [Single5] and write".to_string(),
+                "take [Single5] and roll on".to_string()
+            )
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use std::path::Path;
+
+use anyhow::{bail, ensure};
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    deck::get_official_cards,
+    players::legal_moves,
+    recording::{Decision, Position},
+    tournament::MAX_TURNS,
+    Card,
+    Card::*,
+    GameState, MyRng, Player, PlayerLog, Rules, TakenDice, Turn, NUMBER_OF_DICE,
+};
+
+/// a situation at the table described in a ron file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Situation {
+    pub card: Card,
+    #[serde(default)]
+    pub taken_dice: Vec<TakenDice>,
+    pub roll: Vec<u8>,
+    /// the points of the earlier cards of the turn
+    #[serde(default)]
+    pub turn_points: i32,
+    /// the points of the earlier tuttos on a FireWork
+    #[serde(default)]
+    pub fire_work_points: i32,
+    /// whether the first tutto on a Clover was made
+    #[serde(default)]
+    pub clover_win_next_tutto: bool,
+    pub scores: Vec<i32>,
+    /// the seat to decide
+    #[serde(default)]
+    pub player: usize,
+    /// the cards of the game
+    #[serde(default = "get_official_cards")]
+    pub cards: Vec<Card>,
+    /// the cards left before the reshuffle, all cards except the open card if None
+    #[serde(default)]
+    pub unseen: Option<Vec<Card>>,
+    #[serde(default)]
+    pub rules: Rules,
+}
+
+impl Situation {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(ron::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// the position of the decision, the earlier turns are left out of the logs
+    pub fn position(&self) -> anyhow::Result<Position> {
+        ensure!(
+            self.card != Card::Stop,
+            "a Stop ends the turn before any roll"
+        );
+        ensure!(
+            self.player < self.scores.len(),
+            "seat {} is not at the table",
+            self.player
+        );
+        ensure!(
+            self.roll.iter().all(|die| (1..=6).contains(die)),
+            "dice show 1 to 6"
+        );
+        if self.card == Flush {
+            ensure!(
+                self.taken_dice
+                    .iter()
+                    .all(|dice| matches!(dice, TakenDice::SingleFlush(1..=6))),
+                "a Flush sets aside SingleFlush dice only"
+            );
+            let mut numbers = self.taken_dice.clone();
+            numbers.sort();
+            numbers.dedup();
+            ensure!(
+                numbers.len() == self.taken_dice.len(),
+                "a Flush sets aside every number once"
+            );
+        } else {
+            ensure!(
+                self.taken_dice.iter().all(|dice| matches!(
+                    dice,
+                    TakenDice::Single1 | TakenDice::Single5 | TakenDice::Triple(1..=6)
+                )),
+                "only a Flush sets aside SingleFlush dice"
+            );
+        }
+        let taken: usize = self.taken_dice.iter().map(TakenDice::number_of_dice).sum();
+        ensure!(
+            taken + self.roll.len() == NUMBER_OF_DICE,
+            "the taken dice and the roll need to add up to {NUMBER_OF_DICE} dice"
+        );
+
+        let unseen = match &self.unseen {
+            Some(unseen) => unseen.clone(),
+            None => {
+                let mut unseen = self.cards.clone();
+                if let Some(idx) = unseen.iter().position(|card| *card == self.card) {
+                    unseen.remove(idx);
+                }
+                unseen
+            }
+        };
+        // whatever is not left to draw was seen, the open card last
+        let mut seen = self.cards.clone();
+        for card in unseen.iter().chain([&self.card]) {
+            let Some(idx) = seen.iter().position(|c| c == card) else {
+                bail!("the deck holds too few {card} for the unseen cards and the open card");
+            };
+            seen.remove(idx);
+        }
+        seen.push(self.card);
+
+        Ok(Position {
+            turn_number: self.player,
+            scores: self.scores.clone(),
+            logs: vec![PlayerLog::new(); self.scores.len()],
+            deck_new: unseen,
+            deck_seen: seen,
+            card: self.card,
+            taken_dice: self.taken_dice.clone(),
+            previous_cards_total: self.turn_points,
+            fire_work_points: self.fire_work_points,
+            clover_win_next_tutto: self.clover_win_next_tutto,
+            achieved_minus: 0,
+            card_logs: Vec::new(),
+            roll: self.roll.clone(),
+            card_is_finished: false,
+            rules: self.rules.clone(),
+        })
+    }
+}
+
+/// how far the rollouts are played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Horizon {
+    /// to the end of the turn, the value is the change of the lead in points
+    Turn,
+    /// to the end of the game with the policy in every seat, the value is the win probability
+    Game,
+}
+
+/// the estimated value of one legal option
+#[derive(Debug, Clone)]
+pub struct OptionValue {
+    pub decision: Decision,
+    pub value: f64,
+    pub standard_error: f64,
+}
+
+impl OptionValue {
+    /// the option in words
+    pub fn describe(&self, turn: &Turn) -> String {
+        describe(&self.decision, turn)
+    }
+}
+
+/// a decision in words
+pub fn describe(decision: &Decision, turn: &Turn) -> String {
+    match decision {
+        Decision::Move(this_move) => {
+            let dice = turn.dice_of(&this_move.takes);
+            if this_move.write && turn.card != Clover {
+                format!("take {dice:?} and write")
+            } else {
+                format!("take {dice:?} and roll on")
+            }
+        }
+        Decision::Card(true) => "draw a new card".into(),
+        Decision::Card(false) => "stop after the tutto".into(),
+    }
+}
+
+/// whether the decisions set aside the same dice and write
+/// a write is ignored on a Clover, on a tutto it gives up the tutto
+pub fn same_decision(turn: &Turn, a: &Decision, b: &Decision) -> bool {
+    match (a, b) {
+        (Decision::Card(a), Decision::Card(b)) => a == b,
+        (Decision::Move(a), Decision::Move(b)) => {
+            turn.dice_of(&a.takes) == turn.dice_of(&b.takes)
+                && (turn.card == Clover || a.write == b.write)
+        }
+        _ => false,
+    }
+}
+
+/// the legal options of the decision of the turn
+/// after a tutto these are drawing or not, otherwise the legal moves of the roll
+/// empty if the roll busted
+pub fn options(turn: &Turn) -> Vec<Decision> {
+    if turn.card_is_finished {
+        vec![Decision::Card(true), Decision::Card(false)]
+    } else if !turn.contains_valid_dice() {
+        Vec::new()
+    } else {
+        legal_moves(turn).into_iter().map(Decision::Move).collect()
+    }
+}
+
+/// estimates every legal option by rollouts, the best option first
+///
+/// the hidden order of the deck is resampled for every rollout and all options
+/// share the same seeds to make the comparison fairer.
+/// the policy decides everything after the option, an empty list means the roll busted
+pub fn evaluate(
+    state: &GameState,
+    turn: &Turn,
+    policy: &mut dyn Player,
+    horizon: Horizon,
+    samples: usize,
+    rng: &mut MyRng,
+) -> Vec<OptionValue> {
+    let seeds: Vec<u64> = (0..samples).map(|_| rng.gen()).collect();
+    let mut values: Vec<_> = options(turn)
+        .into_iter()
+        .map(|decision| {
+            let (mut sum, mut sum_of_squares) = (0.0, 0.0);
+            for seed in &seeds {
+                let rng = &mut MyRng::seed_from_u64(*seed);
+                let value = rollout(state, turn, &decision, policy, horizon, rng);
+                sum += value;
+                sum_of_squares += value * value;
+            }
+            let n = samples.max(1) as f64;
+            let mean = sum / n;
+            OptionValue {
+                decision,
+                value: mean,
+                standard_error: ((sum_of_squares / n - mean * mean).max(0.0) / n).sqrt(),
+            }
+        })
+        .collect();
+    values.sort_by(|a, b| b.value.total_cmp(&a.value));
+    values
+}
+
+fn rollout(
+    state: &GameState,
+    turn: &Turn,
+    decision: &Decision,
+    policy: &mut dyn Player,
+    horizon: Horizon,
+    rng: &mut MyRng,
+) -> f64 {
+    let me = state.get_player_idx();
+    let mut sim = state.determinize(rng);
+    match decision {
+        Decision::Move(this_move) => {
+            sim.continue_turn_with_move(policy, turn.clone(), this_move.clone(), rng)
+        }
+        Decision::Card(new_card) => {
+            sim.continue_turn_with_card(policy, turn.clone(), *new_card, rng)
+        }
+    };
+    match horizon {
+        Horizon::Turn => (sim.lead(me) - state.lead(me)) as f64,
+        Horizon::Game => {
+            for _ in 0..MAX_TURNS {
+                if sim.winner().is_some() {
+                    break;
+                }
+                sim.next_turn(policy, rng);
+            }
+            (sim.winner() == Some(me)) as u8 as f64
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{ThresholdPlayer, TurnEnd};
+
+    /// one die is left to tutto a Bonus(300) with 750 points set aside
+    pub(crate) fn last_die() -> Situation {
+        Situation {
+            card: Bonus(300),
+            taken_dice: vec![TakenDice::Triple(6), TakenDice::Single1, TakenDice::Single5],
+            roll: vec![5],
+            turn_points: 0,
+            fire_work_points: 0,
+            clover_win_next_tutto: false,
+            scores: vec![0, 0],
+            player: 0,
+            cards: get_official_cards(),
+            unseen: None,
+            rules: Rules::default(),
+        }
+    }
+
+    #[test]
+    fn writing_on_a_tutto_gives_up_the_bonus() {
+        let (state, turn) = last_die().position().unwrap().restore();
+        let values = evaluate(
+            &state,
+            &turn,
+            &mut ThresholdPlayer::default(),
+            Horizon::Turn,
+            20,
+            &mut MyRng::seed_from_u64(0),
+        );
+        let described: Vec<_> = values.iter().map(|v| v.describe(&turn)).collect();
+        assert_eq!(
+            described,
+            ["take [Single5] and roll on", "take [Single5] and write"]
+        );
+        // the policy stops on 1100 points, the write keeps the 800 points set aside
+        assert_eq!(values[0].value, 1100.0);
+        assert_eq!(values[1].value, 800.0);
+        assert!(!same_decision(
+            &turn,
+            &values[0].decision,
+            &values[1].decision
+        ));
+
+        let mut sim = state.clone();
+        let Decision::Move(write) = &values[1].decision else {
+            panic!("a roll offers moves");
+        };
+        let summary = sim.continue_turn_with_move(
+            &mut ThresholdPlayer::default(),
+            turn.clone(),
+            write.clone(),
+            &mut MyRng::seed_from_u64(0),
+        );
+        assert!(matches!(summary.end, TurnEnd::Wrote { points: 800, .. }));
+    }
+
+    #[test]
+    fn taken_dice_need_to_fit_the_card() {
+        let error = |situation: Situation| situation.position().unwrap_err().to_string();
+        let mut bonus = last_die();
+        bonus.taken_dice = vec![TakenDice::SingleFlush(3)];
+        bonus.roll = vec![1, 2, 3, 4, 5];
+        assert_eq!(error(bonus), "only a Flush sets aside SingleFlush dice");
+
+        let mut flush = last_die();
+        flush.card = Flush;
+        flush.cards = vec![Flush; 5];
+        assert_eq!(
+            error(flush.clone()),
+            "a Flush sets aside SingleFlush dice only"
+        );
+        flush.taken_dice = [1, 2, 2, 4, 5].map(TakenDice::SingleFlush).to_vec();
+        assert_eq!(error(flush.clone()), "a Flush sets aside every number once");
+        flush.taken_dice = [1, 2, 3, 4, 5].map(TakenDice::SingleFlush).to_vec();
+        flush.roll = vec![6];
+        assert!(flush.position().is_ok());
+    }
+
+    #[test]
+    fn unseen_cards_need_to_be_in_the_deck() {
+        let mut situation = last_die();
+        situation.unseen = Some(vec![Stop; 10]);
+        assert!(situation.position().is_ok());
+        situation.unseen = Some(vec![Stop; 11]);
+        assert_eq!(
+            situation.position().unwrap_err().to_string(),
+            "the deck holds too few Stop for the unseen cards and the open card"
+        );
+        situation.unseen = Some(vec![Bonus(300); 5]);
+        assert!(situation.position().is_err());
+    }
+}
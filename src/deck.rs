@@ -5,7 +5,7 @@ use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 use Card::*;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 pub enum Card {
     Bonus(i32),
     Double,
@@ -23,7 +23,7 @@ impl Display for Card {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Deck {
     new: Vec<Card>,
     seen: Vec<Card>,
@@ -31,17 +31,17 @@ pub struct Deck {
 
 pub fn get_official_cards() -> Vec<Card> {
     let mut deck = Vec::with_capacity(56);
-    deck.extend([Bonus(200); 5].into_iter());
-    deck.extend([Bonus(300); 5].into_iter());
-    deck.extend([Bonus(400); 5].into_iter());
-    deck.extend([Bonus(500); 5].into_iter());
-    deck.extend([Bonus(600); 5].into_iter());
-    deck.extend([Double; 5].into_iter());
-    deck.extend([FireWork; 5].into_iter());
-    // deck.extend([Flush; 5].into_iter());
-    deck.extend([Clover; 1].into_iter());
-    deck.extend([Stop; 10].into_iter());
-    deck.extend([PlusMinus; 5].into_iter());
+    deck.extend([Bonus(200); 5]);
+    deck.extend([Bonus(300); 5]);
+    deck.extend([Bonus(400); 5]);
+    deck.extend([Bonus(500); 5]);
+    deck.extend([Bonus(600); 5]);
+    deck.extend([Double; 5]);
+    deck.extend([FireWork; 5]);
+    // deck.extend([Flush; 5]);
+    deck.extend([Clover; 1]);
+    deck.extend([Stop; 10]);
+    deck.extend([PlusMinus; 5]);
     deck
 }
 
@@ -64,7 +64,72 @@ impl Deck {
         card
     }
 
+    /// puts a deck back together, the last seen card is the open one
+    pub(crate) fn from_piles(new: Vec<Card>, seen: Vec<Card>) -> Self {
+        Self { new, seen }
+    }
+
+    /// the cards left before the reshuffle in the order they will be drawn
+    pub(crate) fn unseen(&self) -> &[Card] {
+        &self.new
+    }
+
+    /// returns a copy of the deck where the order of the unseen cards is resampled
+    pub fn determinize(&self, rng: &mut MyRng) -> Self {
+        let mut new = self.new.clone();
+        new.shuffle(rng);
+        Self {
+            new,
+            seen: self.seen.clone(),
+        }
+    }
+
     pub fn open_card(&self) -> Card {
         *self.seen.last().unwrap()
     }
 }
+
+/// the public history of the deck
+impl Deck {
+    /// the cards drawn since the last shuffle, the open card is last
+    pub fn seen_since_shuffle(&self) -> &[Card] {
+        &self.seen
+    }
+
+    /// how many cards can be drawn before the deck is reshuffled
+    pub fn draws_until_reshuffle(&self) -> usize {
+        self.new.len()
+    }
+
+    /// how many of each card are left before the reshuffle
+    pub fn remaining(&self) -> Vec<(Card, usize)> {
+        count(&self.new)
+    }
+
+    /// the probability of each card being the next one drawn
+    /// if no cards are left the whole deck is reshuffled
+    pub fn next_card_odds(&self) -> Vec<(Card, f64)> {
+        let counts = if self.new.is_empty() {
+            count(&self.seen)
+        } else {
+            count(&self.new)
+        };
+        let total: usize = counts.iter().map(|(_, n)| n).sum();
+        counts
+            .into_iter()
+            .map(|(card, n)| (card, n as f64 / total as f64))
+            .collect()
+    }
+}
+
+/// counts the cards in order of their first appearance
+fn count(cards: &[Card]) -> Vec<(Card, usize)> {
+    let mut counts: Vec<(Card, usize)> = Vec::new();
+    for card in cards {
+        match counts.iter_mut().find(|(c, _)| c == card) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((*card, 1)),
+        }
+    }
+    counts
+}
@@ -0,0 +1,71 @@
+use crate::{Card, Card::*, GameState, Turn, NUMBER_OF_DICE};
+
+/// the length of the encodings
+pub const NUMBER_OF_FEATURES: usize = 14;
+
+/// encodes a move decision, assuming everything offered is taken
+pub fn encode_move(state: &GameState, turn: &Turn) -> Vec<f32> {
+    let dice = turn.dice_of(&turn.categorize_roll());
+    encode(
+        state,
+        turn,
+        false,
+        turn.dice_left_after(&dice),
+        turn.points_after(&dice),
+    )
+}
+
+/// encodes the decision to draw a new card
+pub fn encode_card(state: &GameState, last_turn: &Turn) -> Vec<f32> {
+    encode(
+        state,
+        last_turn,
+        true,
+        NUMBER_OF_DICE,
+        last_turn.previous_cards_total,
+    )
+}
+
+fn encode(
+    state: &GameState,
+    turn: &Turn,
+    card_decision: bool,
+    dice_left: usize,
+    points: i32,
+) -> Vec<f32> {
+    let me = state.get_player_idx();
+    let best_other = state.scores()[me] - state.lead(me);
+    let mut features = Vec::with_capacity(NUMBER_OF_FEATURES);
+    features.extend(card_one_hot(turn.card));
+    features.push(if let Bonus(n) = turn.card {
+        n as f32 / 600.0
+    } else {
+        0.0
+    });
+    features.push(card_decision as u8 as f32);
+    features.push(dice_left as f32 / NUMBER_OF_DICE as f32);
+    features.push(turn.roll.len() as f32 / NUMBER_OF_DICE as f32);
+    features.push(points as f32 / 1000.0);
+    features.push(turn.previous_cards_total as f32 / 1000.0);
+    let goal = state.rules().point_goal as f32;
+    features.push(state.scores()[me] as f32 / goal);
+    features.push(best_other as f32 / goal);
+    debug_assert_eq!(features.len(), NUMBER_OF_FEATURES);
+    features
+}
+
+/// one hot encoding of the playable cards
+fn card_one_hot(card: Card) -> [f32; 6] {
+    let mut one_hot = [0.0; 6];
+    let idx = match card {
+        Bonus(_) => 0,
+        Double => 1,
+        FireWork => 2,
+        Flush => 3,
+        Clover => 4,
+        PlusMinus => 5,
+        Stop => return one_hot,
+    };
+    one_hot[idx] = 1.0;
+    one_hot
+}
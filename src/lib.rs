@@ -1,28 +1,36 @@
-use std::{fmt::Display, usize};
+use std::fmt::Display;
 
 use rand::{Rng, SeedableRng};
 use rand_pcg::Pcg64Mcg as MyRng;
 use rand_seeder::Seeder;
+use serde::{Deserialize, Serialize};
 
+pub mod analysis;
 pub mod deck;
+pub mod features;
 pub mod logging;
 pub mod players;
+pub mod recording;
+pub mod tournament;
+pub mod training;
 
 pub use deck::{Card, Deck};
-pub use logging::{CardLog, PlayerLog, TurnLog};
-pub use players::NaivePlayer;
+pub use logging::{CardLog, GameResult, PlayerLog, TurnEnd, TurnLog, TurnSummary};
 use players::Player;
+pub use players::{MctsPlayer, NaivePlayer, ThresholdPlayer};
+use tournament::MAX_TURNS;
 use Card::*;
 
 pub const POINT_GOAL: i32 = 10_000;
 pub const NUMBER_OF_DICE: usize = 6;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Move {
     takes: Vec<Take>,
     write: bool,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Take {
     Single(usize, u8),
     Triple(usize, usize, usize, u8),
@@ -63,7 +71,7 @@ impl Display for Take {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TakenDice {
     Single5,
     Single1,
@@ -106,6 +114,7 @@ impl Display for TakenDice {
     }
 }
 
+#[derive(Clone)]
 pub struct Turn {
     card: Card,
     taken_dice: Vec<TakenDice>,
@@ -116,6 +125,7 @@ pub struct Turn {
     logs: Vec<CardLog>,
     roll: Vec<u8>,
     card_is_finished: bool,
+    end: Option<TurnEnd>,
 }
 
 impl Turn {
@@ -131,10 +141,17 @@ impl Turn {
             logs: Vec::new(),
             roll: Vec::new(),
             card_is_finished: true,
+            end: None,
         }
     }
 }
 
+impl Default for Turn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Turn {
     /// accepts a new card and resets the dice
     /// assumes Tutto
@@ -177,11 +194,9 @@ impl Turn {
                         self.taken_dice.iter().map(|x| x.points()).sum::<i32>();
                     self.taken_dice = Vec::new();
                 }
-                Clover => {
-                    if !self.clover_win_next_tutto {
-                        self.clover_win_next_tutto = true;
-                        self.taken_dice = Vec::new();
-                    }
+                Clover if !self.clover_win_next_tutto => {
+                    self.clover_win_next_tutto = true;
+                    self.taken_dice = Vec::new();
                 }
                 PlusMinus => {
                     self.achieved_minus += 1;
@@ -224,7 +239,7 @@ impl Turn {
         } else {
             let mut taken_idxs = Vec::new();
             let mut takes = Vec::new();
-            for i in (2..=6).chain([1].into_iter()) {
+            for i in (2..=6).chain([1]) {
                 let mut triplets = self.search_triplet(i);
                 for chunk in triplets.chunks(3) {
                     takes.push(Take::Triple(chunk[0], chunk[1], chunk[2], i))
@@ -327,7 +342,37 @@ impl Turn {
         if self.card == FireWork {
             res += self.fire_work_points;
         }
-        return res;
+        res
+    }
+
+    /// returns how many dice are left after setting aside the dice
+    pub fn dice_left_after(&self, dice: &[TakenDice]) -> usize {
+        self.number_of_dice_left() - dice.iter().map(|x| x.number_of_dice()).sum::<usize>()
+    }
+
+    /// returns the points of the turn at risk after setting aside the dice
+    pub fn points_after(&self, dice: &[TakenDice]) -> i32 {
+        let mut points = self.previous_cards_total + self.this_card_points();
+        if self.card != Flush {
+            points += dice.iter().map(|x| x.points()).sum::<i32>();
+        }
+        points
+    }
+
+    /// returns the sorted dice the takes would set aside
+    pub fn dice_of(&self, takes: &[Take]) -> Vec<TakenDice> {
+        let mut dice: Vec<_> = takes
+            .iter()
+            .map(|take| {
+                if self.card == Flush {
+                    take.into_flush_dice()
+                } else {
+                    take.into_taken_dice()
+                }
+            })
+            .collect();
+        dice.sort();
+        dice
     }
 
     pub fn takes_string(&self) -> String {
@@ -373,17 +418,17 @@ impl Turn {
     }
 
     /// sums the points and applies the tutto action.
-    fn finish_card(&mut self) {
+    fn finish_card(&mut self, rules: &Rules) {
         debug_assert!(!self.card_is_finished);
         let mut new_points = self.this_card_points();
         match self.card {
             Bonus(n) => new_points += n,
             Double => new_points *= 2,
             FireWork => new_points += self.fire_work_points,
-            Flush => (),
-            Clover => new_points = POINT_GOAL,
+            Flush => new_points += rules.flush_reward,
+            Clover => new_points = rules.point_goal,
             Stop => unreachable!(),
-            PlusMinus => new_points = 1000,
+            PlusMinus => new_points = rules.plus_minus,
         }
         self.logs.push(CardLog {
             card: self.card,
@@ -421,37 +466,112 @@ impl Turn {
     }
 }
 
-pub struct Game {
-    players: Vec<Box<dyn Player>>,
-    rng: Option<MyRng>,
+/// variants of the rules, the default are the official rules
+/// the composition of the deck is given by the cards of the game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Rules {
+    /// the score needed to win, also the points of a Clover
+    pub point_goal: i32,
+    /// the points of a PlusMinus tutto and what the leaders lose
+    pub plus_minus: i32,
+    /// the points of a Flush tutto
+    pub flush_reward: i32,
+    /// once a player reached the point_goal the round is finished
+    /// and the highest score wins, ties go to the earlier seat
+    pub finish_round: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            point_goal: POINT_GOAL,
+            plus_minus: 1000,
+            flush_reward: 0,
+            finish_round: false,
+        }
+    }
+}
+
+/// everything about a running game except the players
+/// can be cloned to simulate the game ahead
+#[derive(Clone)]
+pub struct GameState {
     turn: usize,
     log: Vec<PlayerLog>,
     deck: Deck,
     scores: Vec<i32>,
+    rules: Rules,
+}
+
+pub struct Game {
+    players: Vec<Box<dyn Player>>,
+    state: GameState,
+    rng: MyRng,
+}
+
+/// creates the rng from a seed or from entropy
+pub fn make_rng(seed: Option<&str>) -> MyRng {
+    if let Some(seed) = seed {
+        Seeder::from(seed).make_rng()
+    } else {
+        MyRng::from_entropy()
+    }
 }
 
 /// contructors
 impl Game {
-    pub fn new(players: Vec<Box<dyn Player>>, cards: Vec<Card>, seed: Option<&str>) -> Self {
-        let mut rng: MyRng = if let Some(seed) = seed {
-            Seeder::from(seed).make_rng()
-        } else {
-            MyRng::from_entropy()
-        };
-        let deck = Deck::shuffle_from_vec(cards, &mut rng);
+    /// tells the players their seats
+    pub fn new(mut players: Vec<Box<dyn Player>>, cards: Vec<Card>, seed: Option<&str>) -> Self {
+        let mut rng = make_rng(seed);
+        let state = GameState::new(players.len(), cards, &mut rng);
+        let n = players.len();
+        for (seat, player) in players.iter_mut().enumerate() {
+            player.on_game_start(seat, n);
+        }
         Self {
-            log: (0..players.len()).map(|_| PlayerLog::new()).collect(),
-            scores: vec![0; players.len()],
             players,
-            rng: Some(rng),
+            state,
+            rng,
+        }
+    }
+}
+
+impl GameState {
+    pub fn new(number_of_players: usize, cards: Vec<Card>, rng: &mut MyRng) -> Self {
+        Self::with_rules(number_of_players, cards, Rules::default(), rng)
+    }
+
+    pub fn with_rules(
+        number_of_players: usize,
+        cards: Vec<Card>,
+        rules: Rules,
+        rng: &mut MyRng,
+    ) -> Self {
+        Self {
+            log: (0..number_of_players).map(|_| PlayerLog::new()).collect(),
+            scores: vec![0; number_of_players],
             turn: 0,
-            deck,
+            deck: Deck::shuffle_from_vec(cards, rng),
+            rules,
+        }
+    }
+
+    /// returns a copy of the state where the hidden order of the deck is resampled
+    pub fn determinize(&self, rng: &mut MyRng) -> Self {
+        Self {
+            turn: self.turn,
+            log: self.log.clone(),
+            deck: self.deck.determinize(rng),
+            scores: self.scores.clone(),
+            rules: self.rules.clone(),
         }
     }
 }
 
 /// game state
-impl Game {
+impl GameState {
+    /// the highest score and every player with it, each listed once
     pub fn highest_score(&self) -> (i32, Vec<usize>) {
         let mut players = Vec::new();
         let mut highest_score = 0;
@@ -459,26 +579,60 @@ impl Game {
             if highest_score < *score {
                 highest_score = *score;
                 players = vec![p]
-            }
-            if highest_score == *score {
+            } else if highest_score == *score {
                 players.push(p)
             }
         }
         (highest_score, players)
     }
 
+    pub fn scores(&self) -> &[i32] {
+        &self.scores
+    }
+
+    /// returns the lead of the player over the best other player
+    pub fn lead(&self, player: usize) -> i32 {
+        let best_other = self
+            .scores
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != player)
+            .map(|(_, score)| *score)
+            .max()
+            .unwrap_or(0);
+        self.scores[player] - best_other
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// returns the player who reached the point_goal of the rules
+    /// when the round is finished the winner is only known at the end of the round
+    pub fn winner(&self) -> Option<usize> {
+        let goal = self.rules.point_goal;
+        if !self.rules.finish_round {
+            return self.scores.iter().position(|score| *score >= goal);
+        }
+        let (highest, players) = self.highest_score();
+        (highest >= goal && self.turn.is_multiple_of(self.scores.len())).then(|| players[0])
+    }
+
     pub fn card(&self) -> Card {
         self.deck.open_card()
     }
 
-    /// gets the index of the player currently playing
-    pub fn get_player_idx(&self) -> usize {
-        self.turn % self.players.len()
+    pub fn deck(&self) -> &Deck {
+        &self.deck
+    }
+
+    pub fn logs(&self) -> &[PlayerLog] {
+        &self.log
     }
 
-    /// gets the current player
-    fn get_current_player(&self) -> &dyn Player {
-        self.players[self.get_player_idx()].as_ref()
+    /// gets the index of the player currently playing
+    pub fn get_player_idx(&self) -> usize {
+        self.turn % self.scores.len()
     }
 
     /// string for cli output
@@ -494,94 +648,262 @@ impl Game {
 }
 
 /// progressing the game state
-impl Game {
-    /// plays the turn
-    pub fn next_turn(&mut self) {
+impl GameState {
+    /// plays the turn of the current player
+    pub fn next_turn(&mut self, player: &mut dyn Player, rng: &mut MyRng) -> TurnSummary {
         // note that the type Turn handles counting points and that the logic
         // for new card happens in the function play_card
         let mut turn = Turn::new();
+        self.play_cards(player, &mut turn, rng);
+        self.end_turn(turn)
+    }
+
+    /// plays the rest of a turn which was interrupted before the move was applied
+    pub fn continue_turn_with_move(
+        &mut self,
+        player: &mut dyn Player,
+        mut turn: Turn,
+        this_move: Move,
+        rng: &mut MyRng,
+    ) -> TurnSummary {
+        let turn_ends = match self.apply_move(player, &mut turn, this_move, rng) {
+            Some(turn_ends) => turn_ends,
+            None => self.play_card(player, &mut turn, rng),
+        };
+        if !turn_ends {
+            self.play_cards(player, &mut turn, rng);
+        }
+        self.end_turn(turn)
+    }
+
+    /// plays the rest of a turn which was interrupted after a tutto
+    pub fn continue_turn_with_card(
+        &mut self,
+        player: &mut dyn Player,
+        mut turn: Turn,
+        new_card: bool,
+        rng: &mut MyRng,
+    ) -> TurnSummary {
+        if new_card {
+            self.play_cards(player, &mut turn, rng);
+        } else {
+            turn.end = Some(TurnEnd::Passed {
+                points: turn.previous_cards_total,
+            });
+        }
+        self.end_turn(turn)
+    }
+
+    /// the outcome of the game so far
+    pub fn result(&self) -> GameResult {
+        GameResult {
+            winner: self.winner(),
+            scores: self.scores.clone(),
+            turns: self.turn,
+        }
+    }
+
+    /// draws cards until the turn needs to end
+    fn play_cards(&mut self, player: &mut dyn Player, turn: &mut Turn, rng: &mut MyRng) {
         loop {
-            turn.new_card(self.deck.draw_new(self.rng.as_mut().unwrap()));
-            println!("card: {:?}", self.card());
-            if self.deck.open_card() == Stop {
+            turn.new_card(self.deck.draw_new(rng));
+            if self.card() == Stop {
                 turn.set_failed();
-                break;
+                turn.end = Some(TurnEnd::Stopped);
+                return;
             }
-            if self.deck.open_card() == PlusMinus
-                && self.highest_score().1.contains(&self.get_player_idx())
-            {
+            if self.card() == PlusMinus && self.highest_score().1.contains(&self.get_player_idx()) {
                 turn.set_failed();
-                break;
+                turn.end = Some(TurnEnd::Stopped);
+                return;
             }
-            if self.play_card(&mut turn) {
-                break;
+            if self.play_card(player, turn, rng) {
+                return;
             }
         }
+    }
 
+    /// returns true if the turn needs to end
+    /// additionally guarantees that the turn is card-finished
+    fn play_card(&mut self, player: &mut dyn Player, turn: &mut Turn, rng: &mut MyRng) -> bool {
+        loop {
+            turn.roll_dice(rng);
+            if !turn.contains_valid_dice() {
+                turn.end = Some(TurnEnd::Busted {
+                    card: turn.card,
+                    points: turn.points_after(&[]),
+                });
+                turn.set_failed();
+                return true;
+            }
+            let this_move = player.make_move(self, turn, rng);
+            if let Some(turn_ends) = self.apply_move(player, turn, this_move, rng) {
+                return turn_ends;
+            }
+        }
+    }
+
+    /// returns None if the dice need to be rolled again
+    /// otherwise returns true if the turn needs to end
+    fn apply_move(
+        &self,
+        player: &mut dyn Player,
+        turn: &mut Turn,
+        this_move: Move,
+        rng: &mut MyRng,
+    ) -> Option<bool> {
+        turn.take_dice(this_move.takes);
+        if this_move.write && turn.card != Clover {
+            turn.end = Some(TurnEnd::Wrote {
+                card: turn.card,
+                points: turn.points_after(&[]),
+                dice_left: turn.number_of_dice_left(),
+            });
+            turn.write_points();
+            return Some(true);
+        }
+        if turn.is_tutto() {
+            turn.finish_card(&self.rules);
+            if [Clover, PlusMinus].contains(&self.card()) {
+                turn.end = Some(TurnEnd::Finished { card: turn.card });
+                return Some(true);
+            }
+            if player.card_strat(self, turn, rng) {
+                return Some(false);
+            }
+            turn.end = Some(TurnEnd::Passed {
+                points: turn.previous_cards_total,
+            });
+            return Some(true);
+        }
+        None
+    }
+
+    /// applies the points of the finished turn
+    fn end_turn(&mut self, mut turn: Turn) -> TurnSummary {
+        let mut minus = Vec::new();
         for _ in 0..turn.achieved_minus {
             for idx in self.highest_score().1 {
-                self.log[idx].push(TurnLog::Minus1000);
-                self.scores[idx] -= 1000;
+                self.log[idx].push(TurnLog::Minus(self.rules.plus_minus));
+                self.scores[idx] -= self.rules.plus_minus;
+                minus.push(idx);
             }
         }
 
         let idx = self.get_player_idx();
+        let end = turn
+            .end
+            .take()
+            .expect("every way to end a turn sets the end");
+        let cards = turn.logs.clone();
         let (points, log) = turn.finish_turn();
         self.log[idx].push(log);
         self.scores[idx] += points;
         self.turn += 1;
+        TurnSummary {
+            player: idx,
+            turn_number: self.turn - 1,
+            cards,
+            points,
+            minus,
+            end,
+            scores: self.scores.clone(),
+        }
     }
+}
 
-    /// returns true if the turn needs to end
-    /// additionally guarantees that the turn is card-finished
-    fn play_card(&mut self, turn: &mut Turn) -> bool {
-        loop {
-            turn.roll_dice(self.rng.as_mut().unwrap());
-            if !turn.contains_valid_dice() {
-                turn.set_failed();
-                return true;
-            }
-            let mut rng = self.rng.take().unwrap();
-            let this_move = self.get_current_player().make_move(&self, &*turn, &mut rng);
-            self.rng = Some(rng);
-            turn.take_dice(this_move.takes);
-            if this_move.write && !(turn.card == Clover) {
-                turn.write_points();
-                return true;
-            }
-            if turn.is_tutto() {
-                turn.finish_card();
-                if [Clover, PlusMinus].contains(&self.card()) {
-                    return true;
-                }
-                let mut rng = self.rng.take().unwrap();
+impl Game {
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
 
-                let new_card = self
-                    .get_current_player()
-                    .card_strat(&self, &*turn, &mut rng);
-                self.rng = Some(rng);
-                return !new_card;
-            }
+    /// plays the turn and shows its summary to every player
+    pub fn next_turn(&mut self) {
+        let idx = self.state.get_player_idx();
+        let summary = self
+            .state
+            .next_turn(self.players[idx].as_mut(), &mut self.rng);
+        if let Some(log) = self.state.log[idx].last() {
+            println!("{log:?}");
+        }
+        for player in self.players.iter_mut() {
+            player.on_turn_end(&summary);
         }
     }
 
-    /// plays the game until a player reaches the POINT_GOAL
+    /// plays the game until a player reaches the point goal
+    /// gives up after MAX_TURNS like the silent games of the tournament,
+    /// the players learn the result either way
     pub fn play_game(&mut self) {
-        for _ in 0..3 * 4 {
+        for _ in 0..MAX_TURNS {
+            if self.state.winner().is_some() {
+                break;
+            }
             println!("------------------------------------------------");
-            println!("Player {} is playing", self.get_player_idx());
+            println!("Player {} is playing", self.state.get_player_idx());
             self.next_turn();
         }
-        println!("{}", self.get_cli_header())
+        let result = self.state.result();
+        for player in self.players.iter_mut() {
+            player.on_game_end(&result);
+        }
+        println!("{}", self.state.get_cli_header());
+        match self.state.winner() {
+            Some(winner) => println!("Player {winner} won"),
+            None => println!("nobody won within {MAX_TURNS} turns"),
+        }
     }
 
     pub fn save_logs(&self) {
-        for (i, log) in self.log.iter().enumerate() {
+        for (i, log) in self.state.log.iter().enumerate() {
             std::fs::write(
                 format!("out/player{i}.ron"),
-                &ron::ser::to_string_pretty(log, ron::ser::PrettyConfig::default()).unwrap(),
+                ron::ser::to_string_pretty(log, ron::ser::PrettyConfig::default()).unwrap(),
             )
             .unwrap()
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_scores(scores: Vec<i32>) -> GameState {
+        let mut state = GameState::new(
+            scores.len(),
+            deck::get_official_cards(),
+            &mut MyRng::seed_from_u64(0),
+        );
+        state.scores = scores;
+        state
+    }
+
+    #[test]
+    fn highest_score_lists_every_leader_once() {
+        assert_eq!(
+            state_with_scores(vec![2000, 500]).highest_score(),
+            (2000, vec![0])
+        );
+        assert_eq!(
+            state_with_scores(vec![500, 2000]).highest_score(),
+            (2000, vec![1])
+        );
+        assert_eq!(
+            state_with_scores(vec![3000, 1000, 3000]).highest_score(),
+            (3000, vec![0, 2])
+        );
+    }
+
+    #[test]
+    fn plus_minus_takes_its_points_once_from_a_sole_leader() {
+        let mut state = state_with_scores(vec![2000, 500]);
+        state.turn = 1;
+        let mut turn = Turn::new();
+        turn.previous_cards_total = 1000;
+        turn.achieved_minus = 1;
+        turn.end = Some(TurnEnd::Finished { card: PlusMinus });
+        state.end_turn(turn);
+        assert_eq!(state.scores, [1000, 1500]);
+    }
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::Card;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct PlayerLog(Vec<TurnLog>);
 
 impl PlayerLog {
@@ -13,11 +13,20 @@ impl PlayerLog {
     pub fn push(&mut self, log: TurnLog) {
         self.0.push(log)
     }
+
+    pub fn last(&self) -> Option<&TurnLog> {
+        self.0.last()
+    }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum TurnLog {
-    Normal { cards: Vec<CardLog>, total: i32 },
+    Normal {
+        cards: Vec<CardLog>,
+        total: i32,
+    },
+    Minus(i32),
+    /// a loss of 1000 in logs written before the PlusMinus amount was a rule
     Minus1000,
 }
 
@@ -27,8 +36,77 @@ impl TurnLog {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CardLog {
     pub card: Card,
     pub points: i32,
 }
+
+/// how a turn ended
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TurnEnd {
+    /// the player wrote the points at risk
+    /// dice_left is 0 for a write on a tutto, which forgoes the reward of the card
+    Wrote {
+        card: Card,
+        points: i32,
+        dice_left: usize,
+    },
+    /// the player did not draw a new card after a tutto
+    Passed { points: i32 },
+    /// the roll contained no valid dice and the points at risk were lost
+    Busted { card: Card, points: i32 },
+    /// a Stop was drawn or a PlusMinus while leading
+    Stopped,
+    /// a tutto on a Clover or a PlusMinus
+    Finished { card: Card },
+}
+
+/// what every player learns at the end of a turn
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TurnSummary {
+    pub player: usize,
+    pub turn_number: usize,
+    pub cards: Vec<CardLog>,
+    /// the points written, 0 for a failed turn
+    pub points: i32,
+    /// the players who lost the plus_minus points of the rules to a PlusMinus, once per loss
+    pub minus: Vec<usize>,
+    pub end: TurnEnd,
+    /// the scores after the turn
+    pub scores: Vec<i32>,
+}
+
+/// the outcome of a game
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameResult {
+    /// None if nobody reached the goal in time
+    pub winner: Option<usize>,
+    pub scores: Vec<i32>,
+    pub turns: usize,
+}
+
+impl GameResult {
+    /// whether another player has the score of the winner
+    pub fn tied(&self) -> bool {
+        self.winner.is_some_and(|winner| {
+            let score = self.scores[winner];
+            self.scores.iter().filter(|other| **other == score).count() > 1
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logs_with_the_old_minus_still_load() {
+        let log: PlayerLog =
+            ron::from_str("([Normal(cards: [], total: 400), Minus1000, Minus(500)])").unwrap();
+        assert!(matches!(
+            log.0[..],
+            [_, TurnLog::Minus1000, TurnLog::Minus(500)]
+        ));
+    }
+}
@@ -1,14 +1,766 @@
-use tutto_sim::{deck, players::CliPlayer, Game, NaivePlayer};
-fn main() {
+use std::path::Path;
+
+use anyhow::bail;
+use tutto_sim::{
+    analysis::{
+        blunders, card_value,
+        distribution::{self, Bins},
+        exploitability::exploitability,
+        meta_game, plus_minus,
+        probability::{self, TakePolicy},
+        rule_variants::SweepConfig,
+        score_awareness, seat_order, strategy_diff,
+        what_if::{self, Horizon, Situation},
+    },
+    deck, make_rng,
+    players::{
+        CliPlayer, ClonedPlayer, NeuralPlayer, Player, PlayerSpec, PlusMinusAware, QTablePlayer,
+        RulePlayer, ScoreAwarePlayer, ScoreAwareness, ThresholdParams,
+    },
+    recording::{load_records, Recorder},
+    tournament::{win_rates, TournamentConfig},
+    training::{
+        cloning::ClonedPolicy,
+        evolution::{evolve, EvolutionConfig},
+        neural::{self, NeuralConfig},
+        q_learning::{self, QConfig},
+    },
+    Game, NaivePlayer, ThresholdPlayer, NUMBER_OF_DICE,
+};
+
+const USAGE: &str = "commands:
+    play [--record <decisions.ron>]
+    clone <decisions.ron> <policy.ron>
+    evolve <dir> [generations]
+    train-q <table.ron> [games]
+    train-nn <weights.ron> [games]
+    score-aware [games]
+    plus-minus [games]
+    probability
+    card-value [player.ron] [samples]
+    tournament <config.ron>
+    seat-order [config.ron] [games per order]
+    sweep <sweep.ron>
+    meta-game <config.ron> [players per game]
+    exploit <player.ron> <dir> [generations] [players per game]
+    what-if <situation.ron> [turn|game] [samples] [player.ron]
+    annotate <decisions.ron> [threshold] [turn|game] [samples] [player.ron]
+    diff <a.ron> <b.ron> [games] [samples]
+    distribution <config.ron> <out> [point bin] [turn bin]
+    check-rules <rules.ron>";
+
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => play(&[]),
+        Some("play") => play(&args[1..]),
+        Some("clone") => run_cloning(&args[1..]),
+        Some("evolve") => run_evolution(&args[1..]),
+        Some("train-q") => run_q_learning(&args[1..]),
+        Some("train-nn") => run_neural(&args[1..]),
+        Some("score-aware") => run_score_awareness(&args[1..]),
+        Some("plus-minus") => run_plus_minus(&args[1..]),
+        Some("probability") => print_probabilities(),
+        Some("card-value") => run_card_value(&args[1..]),
+        Some("tournament") => run_tournament(&args[1..]),
+        Some("seat-order") => run_seat_order(&args[1..]),
+        Some("sweep") => run_sweep(&args[1..]),
+        Some("meta-game") => run_meta_game(&args[1..]),
+        Some("exploit") => run_exploitability(&args[1..]),
+        Some("what-if") => run_what_if(&args[1..]),
+        Some("annotate") => run_annotation(&args[1..]),
+        Some("diff") => run_diff(&args[1..]),
+        Some("distribution") => run_distribution(&args[1..]),
+        Some("check-rules") => check_rules(&args[1..]),
+        Some(other) => bail!("unknown command {other}\n{USAGE}"),
+    }
+}
+
+/// play [--record <decisions.ron>]
+fn play(args: &[String]) -> anyhow::Result<()> {
+    let human: Box<dyn Player> = match args {
+        [] => Box::new(CliPlayer),
+        [flag, path] if flag == "--record" => Box::new(Recorder::new(CliPlayer, path)?),
+        _ => bail!("usage: play [--record <decisions.ron>]"),
+    };
     let mut game = Game::new(
-        vec![
-            Box::new(NaivePlayer),
-            Box::new(NaivePlayer),
-            Box::new(CliPlayer),
-        ],
+        vec![Box::new(NaivePlayer), Box::new(NaivePlayer), human],
         deck::get_official_cards(),
         None,
     );
     game.play_game();
-    game.save_logs()
+    game.save_logs();
+    Ok(())
+}
+
+/// clone <decisions.ron> <policy.ron>
+fn run_cloning(args: &[String]) -> anyhow::Result<()> {
+    let [records, path] = args else {
+        bail!("usage: clone <decisions.ron> <policy.ron>");
+    };
+    let records = load_records(records)?;
+    let policy = ClonedPolicy::fit(&records);
+    println!(
+        "{} decisions in {} distinct states",
+        records.len(),
+        policy.len()
+    );
+    policy.save(path)?;
+
+    let mut rng = make_rng(None);
+    let mut cloned = ClonedPlayer::load(path)?;
+    println!(
+        "the clone repeats {:.1}% of the recorded decisions",
+        100.0 * cloned.agreement(&records, &mut rng)
+    );
+    let mut baseline = ThresholdPlayer::default();
+    let rates = win_rates(
+        &mut [&mut cloned, &mut baseline],
+        &deck::get_official_cards(),
+        2000,
+        &mut rng,
+    );
+    println!(
+        "win rate against the default ThresholdPlayer: {:.3} to {:.3}",
+        rates[0], rates[1]
+    );
+    Ok(())
+}
+
+/// evolve <dir> [generations]
+fn run_evolution(args: &[String]) -> anyhow::Result<()> {
+    let Some(dir) = args.first() else {
+        bail!("usage: evolve <dir> [generations]");
+    };
+    let mut config = EvolutionConfig::default();
+    if let Some(generations) = args.get(1) {
+        config.generations = generations.parse()?;
+    }
+    std::fs::create_dir_all(dir)?;
+    let mut opponents = [ThresholdPlayer::default(), ThresholdPlayer::default()];
+    let mut opponents: Vec<&mut dyn Player> =
+        opponents.iter_mut().map(|p| p as &mut dyn Player).collect();
+    let best = evolve(&config, &mut opponents, Path::new(dir), &mut make_rng(None))?;
+    println!("{best:#?}");
+    Ok(())
+}
+
+/// train-q <table.ron> [games]
+fn run_q_learning(args: &[String]) -> anyhow::Result<()> {
+    let Some(path) = args.first() else {
+        bail!("usage: train-q <table.ron> [games]");
+    };
+    let mut config = QConfig::default();
+    if let Some(games) = args.get(1) {
+        config.games = games.parse()?;
+    }
+    let mut rng = make_rng(None);
+    q_learning::train(&config, &mut rng).save(path)?;
+
+    let mut learned = QTablePlayer::load(path)?;
+    let mut baseline = ThresholdPlayer::default();
+    let rates = win_rates(
+        &mut [&mut learned, &mut baseline],
+        &config.cards,
+        2000,
+        &mut rng,
+    );
+    println!(
+        "win rate against the default ThresholdPlayer: {:.3} to {:.3}",
+        rates[0], rates[1]
+    );
+    Ok(())
+}
+
+/// train-nn <weights.ron> [games]
+fn run_neural(args: &[String]) -> anyhow::Result<()> {
+    let Some(path) = args.first() else {
+        bail!("usage: train-nn <weights.ron> [games]");
+    };
+    let mut config = NeuralConfig::default();
+    if let Some(games) = args.get(1) {
+        config.games = games.parse()?;
+    }
+    let mut rng = make_rng(None);
+    neural::train(&config, &mut rng).save(path)?;
+
+    let mut learned = NeuralPlayer::load(path)?;
+    let mut baseline = ThresholdPlayer::default();
+    let rates = win_rates(
+        &mut [&mut learned, &mut baseline],
+        &config.cards,
+        2000,
+        &mut rng,
+    );
+    println!(
+        "win rate against the default ThresholdPlayer: {:.3} to {:.3}",
+        rates[0], rates[1]
+    );
+    Ok(())
+}
+
+/// score-aware [games]
+fn run_score_awareness(args: &[String]) -> anyhow::Result<()> {
+    let games = match args.first() {
+        Some(games) => games.parse()?,
+        None => 20_000,
+    };
+    let mut rng = make_rng(None);
+    for players in 2..=4 {
+        let report = score_awareness::compare(
+            &ThresholdParams::default(),
+            &ScoreAwareness::default(),
+            players,
+            games,
+            &deck::get_official_cards(),
+            &mut rng,
+        );
+        println!(
+            "{players} players: blind {:.3}, aware {:.3}, improvement {:+.3} ± {:.3}",
+            report.blind,
+            report.aware,
+            report.improvement(),
+            report.standard_error()
+        );
+    }
+    Ok(())
+}
+
+/// plus-minus [games]
+fn run_plus_minus(args: &[String]) -> anyhow::Result<()> {
+    let games = match args.first() {
+        Some(games) => games.parse()?,
+        None => 10_000,
+    };
+    let candidates: [(&str, Box<dyn Player>); 3] = [
+        ("threshold", Box::new(ThresholdPlayer::default())),
+        (
+            "plus-minus aware",
+            Box::new(PlusMinusAware::new(ThresholdPlayer::default())),
+        ),
+        ("score aware", Box::new(ScoreAwarePlayer::default())),
+    ];
+    let mut rng = make_rng(None);
+    for (name, mut candidate) in candidates {
+        let (mut a, mut b) = (ThresholdPlayer::default(), ThresholdPlayer::default());
+        let mut pool: [&mut dyn Player; 3] = [candidate.as_mut(), &mut a, &mut b];
+        let report = plus_minus::report(&mut pool, &deck::get_official_cards(), games, &mut rng);
+        println!("{name} against two threshold players");
+        println!(
+            "    games with a PlusMinus {:.1}%, winner changed by PlusMinus {:.1}%",
+            100.0 * report.games_with_plus_minus as f64 / games as f64,
+            100.0 * report.changed_winner as f64 / games as f64
+        );
+        for i in 0..pool.len() {
+            println!(
+                "    player {i}: wins {:.3}, PlusMinus per game {:.2}, stolen from per game {:.2}",
+                report.wins[i] as f64 / games as f64,
+                report.achieved[i] as f64 / games as f64,
+                report.stolen_from[i] as f64 / games as f64
+            );
+        }
+    }
+    Ok(())
+}
+
+/// probability
+fn print_probabilities() -> anyhow::Result<()> {
+    println!("normal cards");
+    for dice in 1..=NUMBER_OF_DICE {
+        let table = probability::normal_roll(dice);
+        println!("  {dice} dice: bust {:.2}%", 100.0 * table.bust);
+        for (pattern, p) in &table.patterns {
+            let pattern: Vec<_> = pattern.iter().map(|dice| format!("[{dice}]")).collect();
+            println!("      {:>7.3}%  {}", 100.0 * p, pattern.join(" "));
+        }
+    }
+    println!("Flush");
+    for taken in 0..NUMBER_OF_DICE {
+        let table = probability::flush_roll(taken);
+        let new: Vec<_> = table.new_numbers[1..]
+            .iter()
+            .enumerate()
+            .map(|(i, p)| format!("{}: {:.2}%", i + 1, 100.0 * p))
+            .collect();
+        println!(
+            "  {} dice: bust {:.2}%, new numbers {}",
+            table.dice,
+            100.0 * table.bust,
+            new.join(", ")
+        );
+    }
+    println!("chance to reach the tutto by dice left");
+    for policy in TakePolicy::ALL {
+        for (name, chance) in [
+            ("normal", probability::tutto_chance(policy)),
+            ("Flush", probability::flush_tutto_chance(policy)),
+        ] {
+            let row: Vec<_> = chance[1..]
+                .iter()
+                .map(|p| format!("{:>6.2}%", 100.0 * p))
+                .collect();
+            println!(
+                "  {:<6} {:<6} {}",
+                format!("{policy:?}"),
+                name,
+                row.join(" ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// card-value [player.ron] [samples]
+fn run_card_value(args: &[String]) -> anyhow::Result<()> {
+    let mut player = match args.first() {
+        Some(path) => PlayerSpec::load(path)?.build()?,
+        None => Box::new(ThresholdPlayer::default()),
+    };
+    let samples = match args.get(1) {
+        Some(samples) => samples.parse()?,
+        None => 100_000,
+    };
+    let mut rng = make_rng(None);
+    for value in card_value::report(player.as_mut(), samples, &mut rng) {
+        println!(
+            "{:<10} expected {:>7.1}, tutto {:>5.1}%, bust {:>5.1}%",
+            value.card.to_string(),
+            value.expected_points,
+            100.0 * value.tutto,
+            100.0 * value.bust
+        );
+        let distribution: Vec<_> = value
+            .distribution
+            .iter()
+            .filter(|(_, p)| *p >= 0.005)
+            .map(|(points, p)| format!("{points}: {:.1}%", 100.0 * p))
+            .collect();
+        println!("           {}", distribution.join(", "));
+    }
+    Ok(())
+}
+
+/// tournament <config.ron>
+fn run_tournament(args: &[String]) -> anyhow::Result<()> {
+    let Some(path) = args.first() else {
+        bail!("usage: tournament <config.ron>");
+    };
+    let config = TournamentConfig::load(path)?;
+    let rates = config.run()?;
+    for (spec, rate) in config.players.iter().zip(rates) {
+        println!("{rate:.3} {spec:?}");
+    }
+    Ok(())
+}
+
+/// seat-order [config.ron] [games per order]
+/// without a config three default ThresholdPlayers play
+fn run_seat_order(args: &[String]) -> anyhow::Result<()> {
+    let config = match args.first() {
+        Some(path) => TournamentConfig::load(path)?,
+        None => TournamentConfig {
+            players: vec![PlayerSpec::Threshold(ThresholdParams::default()); 3],
+            games: 2000,
+            seed: None,
+            cards: deck::get_official_cards(),
+        },
+    };
+    let games_per_order = match args.get(1) {
+        Some(games) => games.parse()?,
+        None => config.games,
+    };
+    let mut players = config.build_players()?;
+    let mut players: Vec<&mut dyn Player> =
+        players.iter_mut().map(|p| p as &mut dyn Player).collect();
+    let mut rng = make_rng(config.seed.as_deref());
+    for report in seat_order::compare(&mut players, &config.cards, games_per_order, &mut rng) {
+        let name = if report.rules.finish_round {
+            "finishing the round"
+        } else {
+            "official rules"
+        };
+        println!("{name}, {} games:", report.games);
+        for (seat, rate) in report.by_seat.iter().enumerate() {
+            println!(
+                "    seat {seat}: {:.3} ± {:.3}",
+                rate,
+                report.standard_error(seat)
+            );
+        }
+        println!(
+            "    first seat advantage {:+.3}, draws {:.3}, ties {:.3}",
+            report.first_seat_advantage(),
+            report.draws,
+            report.ties
+        );
+        for (spec, rate) in config.players.iter().zip(&report.by_player) {
+            println!("    {rate:.3} {spec:?}");
+        }
+    }
+    Ok(())
+}
+
+/// sweep <sweep.ron>
+fn run_sweep(args: &[String]) -> anyhow::Result<()> {
+    let Some(path) = args.first() else {
+        bail!("usage: sweep <sweep.ron>");
+    };
+    let config = SweepConfig::load(path)?;
+    for (idx, spec) in config.players.iter().enumerate() {
+        println!("player {idx}: {spec:?}");
+    }
+    let reports = config.run()?;
+    let baseline = reports.first().map(|report| report.ranking());
+    for report in &reports {
+        let ranking = report.ranking();
+        println!("{}", report.variant.name());
+        println!(
+            "    turns {:.1} ± {:.1}, points per turn {:.1} ± {:.1}, draws {:.3}, ties {:.3}",
+            report.mean_turns,
+            report.turns_deviation,
+            report.mean_turn_points,
+            report.turn_points_deviation,
+            report.draws,
+            report.ties
+        );
+        println!(
+            "    by seat {:.3?}, first seat advantage {:+.3}",
+            report.by_seat,
+            report.first_seat_advantage()
+        );
+        println!(
+            "    by player {:.3?}, ranking {ranking:?}{}",
+            report.by_player,
+            if Some(&ranking) == baseline.as_ref() {
+                ""
+            } else {
+                " changed"
+            }
+        );
+    }
+    Ok(())
+}
+
+/// meta-game <config.ron> [players per game]
+/// the games of the config are played for every pairing
+fn run_meta_game(args: &[String]) -> anyhow::Result<()> {
+    let Some(path) = args.first() else {
+        bail!("usage: meta-game <config.ron> [players per game]");
+    };
+    let players_per_game = match args.get(1) {
+        Some(n) => n.parse()?,
+        None => 2,
+    };
+    if players_per_game < 2 {
+        bail!("a game needs at least two players");
+    }
+    let config = TournamentConfig::load(path)?;
+    let mut rng = make_rng(config.seed.as_deref());
+    let matrix = meta_game::payoff_matrix(
+        &config.players,
+        players_per_game,
+        &config.cards,
+        config.games,
+        &mut rng,
+    )?;
+    let equilibrium = matrix.equilibrium(100_000);
+    println!("win rate of the row against the column:");
+    for (idx, row) in matrix.payoff.iter().enumerate() {
+        let cells: Vec<_> = row.iter().map(|rate| format!("{rate:.3}")).collect();
+        println!(
+            "{idx:>3}: {}  mixture {:.3}  {:?}",
+            cells.join(" "),
+            equilibrium.mixture[idx],
+            config.players[idx]
+        );
+    }
+    println!(
+        "the mixture wins {:.3} against itself, exploitable by {:.3}",
+        equilibrium.value, equilibrium.exploitability
+    );
+    for (dominated, by) in matrix.dominated() {
+        println!("{dominated} is dominated by {by}");
+    }
+    Ok(())
+}
+
+/// exploit <player.ron> <dir> [generations] [players per game]
+fn run_exploitability(args: &[String]) -> anyhow::Result<()> {
+    let [path, dir, rest @ ..] = args else {
+        bail!("usage: exploit <player.ron> <dir> [generations] [players per game]");
+    };
+    let opponent = PlayerSpec::load(path)?;
+    let mut config = EvolutionConfig::default();
+    if let Some(generations) = rest.first() {
+        config.generations = generations.parse()?;
+    }
+    let players_per_game = match rest.get(1) {
+        Some(n) => n.parse()?,
+        None => 2,
+    };
+    if players_per_game < 2 {
+        bail!("a game needs at least two players");
+    }
+    let report = exploitability(
+        &opponent,
+        players_per_game,
+        &config,
+        5000,
+        Path::new(dir),
+        &mut make_rng(None),
+    )?;
+    println!("{:#?}", report.best_response);
+    println!(
+        "against {opponent:?} in {players_per_game} player games over {} games:",
+        report.games
+    );
+    println!("    itself          {:.3}", report.self_play);
+    println!("    default         {:.3}", report.default_rate);
+    println!("    best response   {:.3}", report.best_response_rate);
+    println!("    exploitability  {:+.3}", report.exploitability());
+    println!("checkpointed in {}", report.dir.display());
+    Ok(())
+}
+
+/// what-if <situation.ron> [turn|game] [samples] [player.ron]
+/// the rollouts are played by the default ThresholdPlayer without a player
+fn run_what_if(args: &[String]) -> anyhow::Result<()> {
+    let usage = "usage: what-if <situation.ron> [turn|game] [samples] [player.ron]";
+    let Some(path) = args.first() else {
+        bail!(usage);
+    };
+    let horizon = match args.get(1).map(String::as_str) {
+        None | Some("turn") => Horizon::Turn,
+        Some("game") => Horizon::Game,
+        Some(_) => bail!(usage),
+    };
+    let samples = match args.get(2) {
+        Some(samples) => samples.parse()?,
+        None => 10_000,
+    };
+    let mut policy = match args.get(3) {
+        Some(path) => PlayerSpec::load(path)?.build()?,
+        None => Box::new(ThresholdPlayer::default()),
+    };
+    let situation = Situation::load(path)?;
+    let (state, turn) = situation.position()?.restore();
+    let options = what_if::evaluate(
+        &state,
+        &turn,
+        policy.as_mut(),
+        horizon,
+        samples,
+        &mut make_rng(None),
+    );
+    if options.is_empty() {
+        println!("the roll {:?} offers no dice", situation.roll);
+    }
+    for option in options {
+        let action = option.describe(&turn);
+        match horizon {
+            Horizon::Turn => println!(
+                "{:>8.1} ± {:.1} points: {action}",
+                option.value, option.standard_error
+            ),
+            Horizon::Game => println!(
+                "{:>6.2}% ± {:.2} win: {action}",
+                100.0 * option.value,
+                100.0 * option.standard_error
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// annotate <decisions.ron> [threshold] [turn|game] [samples] [player.ron]
+/// the threshold is in points for turn and in percent of win probability for game
+fn run_annotation(args: &[String]) -> anyhow::Result<()> {
+    let usage = "usage: annotate <decisions.ron> [threshold] [turn|game] [samples] [player.ron]";
+    let Some(path) = args.first() else {
+        bail!(usage);
+    };
+    let horizon = match args.get(2).map(String::as_str) {
+        None | Some("turn") => Horizon::Turn,
+        Some("game") => Horizon::Game,
+        Some(_) => bail!(usage),
+    };
+    let threshold = match (args.get(1), horizon) {
+        (Some(threshold), Horizon::Turn) => threshold.parse()?,
+        (Some(threshold), Horizon::Game) => threshold.parse::<f64>()? / 100.0,
+        (None, Horizon::Turn) => 50.0,
+        (None, Horizon::Game) => 0.02,
+    };
+    let samples = match args.get(3) {
+        Some(samples) => samples.parse()?,
+        None => 2000,
+    };
+    let mut policy = match args.get(4) {
+        Some(path) => PlayerSpec::load(path)?.build()?,
+        None => Box::new(ThresholdPlayer::default()),
+    };
+    let records = load_records(path)?;
+    let review = blunders::annotate(
+        &records,
+        policy.as_mut(),
+        horizon,
+        samples,
+        threshold,
+        &mut make_rng(None),
+    );
+    let unit = match horizon {
+        Horizon::Turn => 1.0,
+        Horizon::Game => 100.0,
+    };
+    for blunder in review.blunders() {
+        println!(
+            "record {} turn {} seat {}: {} loses {:.1} to {}",
+            blunder.index,
+            blunder.turn_number,
+            blunder.player,
+            blunder.chosen,
+            unit * blunder.loss,
+            blunder.best
+        );
+    }
+    for index in &review.unmatched {
+        println!("record {index}: the decision is none of the legal options");
+    }
+    for (seat, accuracy) in review.accuracy.iter().enumerate() {
+        if accuracy.decisions > 0 {
+            println!(
+                "seat {seat}: accuracy {:.1}%, {} blunders in {} decisions, mean loss {:.1}",
+                100.0 * accuracy.accuracy(),
+                accuracy.blunders,
+                accuracy.decisions,
+                unit * accuracy.mean_loss()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// diff <a.ron> <b.ron> [games] [samples]
+/// the positions come from games of a against b, the values from rollouts
+/// of the default ThresholdPlayer to the end of the turn, the 20 largest differences are shown
+fn run_diff(args: &[String]) -> anyhow::Result<()> {
+    let [a, b, rest @ ..] = args else {
+        bail!("usage: diff <a.ron> <b.ron> [games] [samples]");
+    };
+    let (spec_a, spec_b) = (PlayerSpec::load(a)?, PlayerSpec::load(b)?);
+    let (mut a, mut b) = (spec_a.build()?, spec_b.build()?);
+    let games = match rest.first() {
+        Some(games) => games.parse()?,
+        None => 20,
+    };
+    let samples = match rest.get(1) {
+        Some(samples) => samples.parse()?,
+        None => 500,
+    };
+    let mut rng = make_rng(None);
+    let positions = strategy_diff::sample_positions(
+        &mut [a.as_mut(), b.as_mut()],
+        &deck::get_official_cards(),
+        games,
+        &mut rng,
+    );
+    let report = strategy_diff::diff(
+        a.as_mut(),
+        b.as_mut(),
+        &positions,
+        &mut ThresholdPlayer::default(),
+        Horizon::Turn,
+        samples,
+        &mut rng,
+    );
+
+    let mut disagreements: Vec<_> = report.disagreements.iter().collect();
+    disagreements.sort_by(|x, y| {
+        let size = |d: &&strategy_diff::Disagreement| d.value_difference.unwrap_or(0.0).abs();
+        size(y).total_cmp(&size(x))
+    });
+    for disagreement in disagreements.iter().take(20) {
+        let position = &disagreement.position;
+        let (decision_a, decision_b) = disagreement.describe();
+        let value = match disagreement.value_difference {
+            Some(value) => format!("{value:+.1}"),
+            None => "?".into(),
+        };
+        println!(
+            "{value:>7} {} roll {:?} taken {:?} at {} points, scores {:?}",
+            position.card,
+            position.roll,
+            position.taken_dice,
+            position.previous_cards_total,
+            position.scores
+        );
+        println!("        a: {decision_a}, b: {decision_b}");
+    }
+    println!("a: {spec_a:?}\nb: {spec_b:?}");
+    for card in &report.by_card {
+        println!(
+            "{:<10} {:>5} of {:>6} positions differ",
+            card.card.to_string(),
+            card.disagreements,
+            card.positions
+        );
+    }
+    println!(
+        "{} of {} positions differ ({:.1}%), a gains {:+.2} points per position",
+        report.disagreements.len(),
+        report.positions,
+        100.0 * report.disagreement_rate(),
+        report.mean_value_difference()
+    );
+    Ok(())
+}
+
+/// distribution <config.ron> <out> [point bin] [turn bin]
+/// writes the histograms to <out>.csv and <out>.json
+fn run_distribution(args: &[String]) -> anyhow::Result<()> {
+    let [path, out, widths @ ..] = args else {
+        bail!("usage: distribution <config.ron> <out> [point bin] [turn bin]");
+    };
+    let mut bins = Bins::default();
+    if let Some(points) = widths.first() {
+        bins.points = points.parse()?;
+    }
+    if let Some(turns) = widths.get(1) {
+        bins.turns = turns.parse()?;
+    }
+    if bins.points <= 0 || bins.turns <= 0 {
+        bail!("the bins need a positive width");
+    }
+    let config = TournamentConfig::load(path)?;
+    let mut players = config.build_players()?;
+    let mut players: Vec<&mut dyn Player> =
+        players.iter_mut().map(|p| p as &mut dyn Player).collect();
+    let mut rng = make_rng(config.seed.as_deref());
+    let distributions =
+        distribution::collect(&mut players, &config.cards, config.games, bins, &mut rng);
+    for (spec, player) in config.players.iter().zip(&distributions.players) {
+        println!(
+            "turn mean {:>6.1}, median {:>5}, 99th percentile {:>5} {spec:?}",
+            player.turn.mean(),
+            player.turn.quantile(0.5).unwrap_or(0),
+            player.turn.quantile(0.99).unwrap_or(0)
+        );
+    }
+    println!(
+        "game length mean {:.1} turns",
+        distributions.game_length.mean()
+    );
+    let out = Path::new(out);
+    distributions.save_csv(out.with_extension("csv"))?;
+    distributions.save_json(out.with_extension("json"))?;
+    Ok(())
+}
+
+/// check-rules <rules.ron>
+fn check_rules(args: &[String]) -> anyhow::Result<()> {
+    let Some(path) = args.first() else {
+        bail!("usage: check-rules <rules.ron>");
+    };
+    RulePlayer::load(path)?;
+    println!("{path} is valid");
+    Ok(())
 }
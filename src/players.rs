@@ -1,4 +1,4 @@
-use crate::{Card::*, Game, Move, MyRng, Turn};
+use crate::{Card::*, GameResult, GameState, Move, MyRng, Turn, TurnSummary};
 
 mod naive;
 pub use naive::NaivePlayer;
@@ -6,16 +6,90 @@ pub use naive::NaivePlayer;
 mod cli_player;
 pub use cli_player::CliPlayer;
 
+mod cloned;
+pub use cloned::ClonedPlayer;
+
+mod card_counting;
+pub use card_counting::{default_estimate, CardCountingPlayer, CardEstimate};
+
+mod mcts;
+pub use mcts::MctsPlayer;
+
+mod meta;
+pub use meta::{MetaPlayer, Selector};
+
+mod neural;
+pub use neural::NeuralPlayer;
+
+mod opponent_model;
+pub use opponent_model::{OpponentAware, OpponentModel, OpponentProfile};
+
+mod plus_minus;
+pub use plus_minus::PlusMinusAware;
+
+mod q_table;
+pub use q_table::QTablePlayer;
+
+mod random;
+pub use random::{legal_moves, Noisy, RandomPlayer};
+
+mod rules;
+pub use rules::{RuleError, RulePlayer, RuleSet};
+
+mod score_aware;
+pub use score_aware::{ScoreAwarePlayer, ScoreAwareness};
+
+mod spec;
+pub use spec::PlayerSpec;
+
+pub mod split;
+
+pub mod take_selection;
+
+mod threshold;
+pub use threshold::{Threshold, ThresholdParams, ThresholdPlayer};
+
 pub trait Player {
-    fn make_move(&self, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move;
-    fn card_strat(&self, state: &Game, last_turn: &Turn, rng: &mut MyRng) -> bool;
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move;
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool;
+
+    /// called before the first turn with the seat of the player
+    fn on_game_start(&mut self, _seat: usize, _n_players: usize) {}
+
+    /// called after the turn of every player, including its own
+    fn on_turn_end(&mut self, _summary: &TurnSummary) {}
+
+    /// called once the game is over
+    fn on_game_end(&mut self, _result: &GameResult) {}
+}
+
+impl Player for Box<dyn Player> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.as_mut().make_move(state, turn, rng)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        self.as_mut().card_strat(state, last_turn, rng)
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        self.as_mut().on_game_start(seat, n_players)
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        self.as_mut().on_turn_end(summary)
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.as_mut().on_game_end(result)
+    }
 }
 
 impl<T> Player for T
 where
     T: SplitPlayer,
 {
-    fn make_move(&self, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
         let card = state.card();
         match card {
             Bonus(num) => self.bonus_strat(num, state, turn, rng),
@@ -24,22 +98,26 @@ where
             Flush => self.flush_strat(state, turn, rng),
             Clover => self.tutto_strat(state, turn, rng),
             PlusMinus => self.plus_minus_strat(state, turn, rng),
-            Stop => unreachable!(),
+            // the game never rolls on a Stop, writing what is offered is the safe answer
+            Stop => Move {
+                takes: turn.categorize_roll(),
+                write: true,
+            },
         }
     }
 
-    fn card_strat(&self, state: &Game, last_turn: &Turn, rng: &mut MyRng) -> bool {
-        <Self as SplitPlayer>::card_strat(&self, state, last_turn, rng)
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        <Self as SplitPlayer>::card_strat(self, state, last_turn, rng)
     }
 }
 
 pub trait SplitPlayer {
-    fn tutto_strat(&self, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move;
-    fn bonus_strat(&self, num: i32, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move;
-    fn double_strat(&self, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move;
-    fn fire_work_strat(&self, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move;
-    fn flush_strat(&self, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move;
-    fn plus_minus_strat(&self, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move;
-
-    fn card_strat(&self, state: &Game, last_turn: &Turn, rng: &mut MyRng) -> bool;
+    fn tutto_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move;
+    fn bonus_strat(&mut self, num: i32, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move;
+    fn double_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move;
+    fn fire_work_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move;
+    fn flush_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move;
+    fn plus_minus_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move;
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool;
 }
@@ -0,0 +1,83 @@
+use crate::{Card, Card::*, GameResult, GameState, Move, MyRng, Player, Turn, TurnSummary};
+
+/// what drawing a card is expected to bring
+#[derive(Debug, Clone, Copy)]
+pub struct CardEstimate {
+    /// the points the card adds on average
+    pub gain: f64,
+    /// the probability that the card loses the points of the turn
+    pub bust: f64,
+}
+
+/// rough estimates for a player taking everything with moderate thresholds
+pub fn default_estimate(card: Card) -> CardEstimate {
+    let (gain, bust) = match card {
+        Bonus(n) => (250.0 + 0.5 * n as f64, 0.25),
+        Double => (500.0, 0.3),
+        FireWork => (600.0, 0.0),
+        Flush => (0.0, 0.6),
+        Clover => (300.0, 0.9),
+        PlusMinus => (700.0, 0.4),
+        Stop => (0.0, 1.0),
+    };
+    CardEstimate { gain, bust }
+}
+
+/// plays the moves of the wrapped player but counts the cards to decide on drawing
+///
+/// a new card is drawn if its expected gain is larger than the expected loss of the points
+/// at risk, weighted by what is left in the deck before the reshuffle
+pub struct CardCountingPlayer<P> {
+    pub player: P,
+    pub estimate: fn(Card) -> CardEstimate,
+}
+
+impl<P: Player> CardCountingPlayer<P> {
+    pub fn new(player: P) -> Self {
+        Self {
+            player,
+            estimate: default_estimate,
+        }
+    }
+
+    /// the expected change of the points when drawing another card
+    pub fn draw_value(&self, state: &GameState, points_at_risk: i32) -> f64 {
+        let is_leader = state.highest_score().1.contains(&state.get_player_idx());
+        state
+            .deck()
+            .next_card_odds()
+            .into_iter()
+            .map(|(card, p)| {
+                let estimate = if card == PlusMinus && is_leader {
+                    // the leader may not play a PlusMinus and loses the turn
+                    (self.estimate)(Stop)
+                } else {
+                    (self.estimate)(card)
+                };
+                p * (estimate.gain - estimate.bust * points_at_risk as f64)
+            })
+            .sum()
+    }
+}
+
+impl<P: Player> Player for CardCountingPlayer<P> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.player.make_move(state, turn, rng)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, _rng: &mut MyRng) -> bool {
+        self.draw_value(state, last_turn.previous_cards_total) > 0.0
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        self.player.on_game_start(seat, n_players)
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        self.player.on_turn_end(summary)
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.player.on_game_end(result)
+    }
+}
@@ -1,14 +1,11 @@
-use std::{
-    io::{stdin, stdout, Write},
-    usize,
-};
+use std::io::{stdin, stdout, Write};
 
-use crate::{Game, Move, MyRng, Player, Turn};
+use crate::{GameState, Move, MyRng, Player, Turn};
 
 pub struct CliPlayer;
 
 impl Player for CliPlayer {
-    fn make_move(&self, state: &Game, turn: &Turn, _rng: &mut MyRng) -> Move {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, _rng: &mut MyRng) -> Move {
         println!("------------------------------------------------");
         println!("{}", state.get_cli_header());
         println!("{}", turn.cli_output());
@@ -62,7 +59,7 @@ impl Player for CliPlayer {
         }
     }
 
-    fn card_strat(&self, _state: &Game, last_turn: &Turn, _rng: &mut MyRng) -> bool {
+    fn card_strat(&mut self, _state: &GameState, last_turn: &Turn, _rng: &mut MyRng) -> bool {
         println!("------------------------------------------------");
         println!("current points: {}", last_turn.previous_cards_total);
         println!("do you want to take a new card? y/[n]");
@@ -82,7 +79,7 @@ impl Player for CliPlayer {
                 return true;
             }
             if buffer == "n" {
-                return true;
+                return false;
             }
             println!("invalid try again!");
         }
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use crate::{
+    recording::{Decision, DecisionRecord},
+    training::{
+        cloning::{classify, ClonedPolicy},
+        q_learning::StateKey,
+    },
+    GameState, Move, MyRng, Player, ThresholdPlayer, Turn,
+};
+
+/// imitates a recorded player
+/// states the recordings never reached are left to the default ThresholdPlayer
+pub struct ClonedPlayer {
+    policy: ClonedPolicy,
+    fallback: ThresholdPlayer,
+}
+
+impl ClonedPlayer {
+    pub fn new(policy: ClonedPolicy) -> Self {
+        Self {
+            policy,
+            fallback: ThresholdPlayer::default(),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self::new(ClonedPolicy::load(path)?))
+    }
+
+    /// the share of recorded decisions the clone makes the same way
+    /// moves are compared by their abstract action
+    pub fn agreement(&mut self, records: &[DecisionRecord], rng: &mut MyRng) -> f64 {
+        let same = records
+            .iter()
+            .filter(|record| {
+                let (state, turn) = record.position.restore();
+                match &record.decision {
+                    Decision::Move(this_move) => {
+                        let own = self.make_move(&state, &turn, rng);
+                        classify(&turn, &own) == classify(&turn, this_move)
+                    }
+                    Decision::Card(new_card) => self.card_strat(&state, &turn, rng) == *new_card,
+                }
+            })
+            .count();
+        same as f64 / records.len().max(1) as f64
+    }
+}
+
+impl Player for ClonedPlayer {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        match self.policy.best_move(&StateKey::for_move(state, turn)) {
+            Some(action) => action.to_move(turn),
+            None => self.fallback.make_move(state, turn, rng),
+        }
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        match self.policy.best_card(&StateKey::for_card(state, last_turn)) {
+            Some(new_card) => new_card,
+            None => self.fallback.card_strat(state, last_turn, rng),
+        }
+    }
+}
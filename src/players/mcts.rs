@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+
+use super::take_selection::take_subsets;
+use crate::{Card, Card::*, GameState, Move, MyRng, Player, TakenDice, Turn};
+
+/// player which decides by monte carlo tree search over the rest of its turn
+///
+/// before every iteration the hidden order of the deck is resampled from what the deck has shown
+/// so far. dice rolls and card draws are chance nodes keyed by their outcome.
+/// the value of a simulated turn is the change of the lead over the best opponent in thousand points.
+pub struct MctsPlayer {
+    iterations: usize,
+    exploration: f64,
+    rollout: Box<dyn Player>,
+}
+
+impl MctsPlayer {
+    pub fn new(iterations: usize, exploration: f64) -> Self {
+        Self {
+            iterations,
+            exploration,
+            rollout: Box::new(Greedy),
+        }
+    }
+
+    /// replaces the policy used after leaving the tree
+    pub fn with_rollout(mut self, rollout: impl Player + 'static) -> Self {
+        self.rollout = Box::new(rollout);
+        self
+    }
+
+    /// runs the search and returns the most visited action at the root
+    fn search(
+        &mut self,
+        state: &GameState,
+        turn: &Turn,
+        actions: Vec<Action>,
+        rng: &mut MyRng,
+    ) -> Action {
+        let me = state.get_player_idx();
+        let before = state.lead(me);
+        let mut walker = Walker {
+            exploration: self.exploration,
+            rollout: self.rollout.as_mut(),
+            tree: vec![Node::new(actions)],
+            path: Vec::new(),
+            in_tree: true,
+        };
+        for _ in 0..self.iterations {
+            let mut sim = state.determinize(rng);
+            let sim_turn = turn.clone();
+            let arm = walker.tree[0].select(walker.exploration);
+            walker.path = vec![(0, arm)];
+            walker.in_tree = true;
+            let action = walker.tree[0].arms[arm].action.clone();
+            match action {
+                Action::Take { .. } => {
+                    let this_move = action.to_move(&sim_turn);
+                    sim.continue_turn_with_move(&mut walker, sim_turn, this_move, rng);
+                }
+                Action::Draw(new_card) => {
+                    sim.continue_turn_with_card(&mut walker, sim_turn, new_card, rng);
+                }
+            }
+            let value = (sim.lead(me) - before) as f64 / 1000.0;
+            for (node, arm) in std::mem::take(&mut walker.path) {
+                let arm = &mut walker.tree[node].arms[arm];
+                arm.visits += 1;
+                arm.total += value;
+            }
+        }
+        let root = walker.tree.swap_remove(0);
+        root.arms
+            .into_iter()
+            .max_by_key(|arm| arm.visits)
+            .expect("there is always an action")
+            .action
+    }
+}
+
+impl Default for MctsPlayer {
+    fn default() -> Self {
+        Self::new(1000, 1.0)
+    }
+}
+
+impl Player for MctsPlayer {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        let actions = take_actions(turn);
+        if actions.len() == 1 {
+            return actions[0].to_move(turn);
+        }
+        self.search(state, turn, actions, rng).to_move(turn)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        match self.search(state, last_turn, draw_actions(), rng) {
+            Action::Draw(new_card) => new_card,
+            Action::Take { .. } => unreachable!(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum Action {
+    Take { dice: Vec<TakenDice>, write: bool },
+    Draw(bool),
+}
+
+impl Action {
+    /// finds the takes of the current roll which set aside the dice of the action
+    fn to_move(&self, turn: &Turn) -> Move {
+        match self {
+            Action::Take { dice, write } => Move {
+                takes: take_subsets(turn)
+                    .into_iter()
+                    .find(|takes| turn.dice_of(takes) == *dice)
+                    .expect("action was generated from this roll"),
+                write: *write,
+            },
+            Action::Draw(_) => unreachable!(),
+        }
+    }
+}
+
+/// the legal moves of the current roll
+/// writing is left out where it is ignored or can only lose the tutto
+fn take_actions(turn: &Turn) -> Vec<Action> {
+    let mut actions = Vec::new();
+    for takes in take_subsets(turn) {
+        let dice = turn.dice_of(&takes);
+        let can_write = turn.card != Clover && turn.dice_left_after(&dice) != 0;
+        if can_write {
+            actions.push(Action::Take {
+                dice: dice.clone(),
+                write: true,
+            });
+        }
+        actions.push(Action::Take { dice, write: false });
+    }
+    actions
+}
+
+fn draw_actions() -> Vec<Action> {
+    vec![Action::Draw(true), Action::Draw(false)]
+}
+
+/// the outcome of a chance node
+#[derive(PartialEq, Eq, Hash)]
+enum Observation {
+    Roll { card: Card, roll: Vec<u8> },
+    Tutto,
+}
+
+impl Observation {
+    fn roll(turn: &Turn) -> Self {
+        let mut roll = turn.roll.clone();
+        roll.sort();
+        Self::Roll {
+            card: turn.card,
+            roll,
+        }
+    }
+}
+
+struct Arm {
+    action: Action,
+    visits: u32,
+    total: f64,
+    children: HashMap<Observation, usize>,
+}
+
+struct Node {
+    arms: Vec<Arm>,
+}
+
+impl Node {
+    fn new(actions: Vec<Action>) -> Self {
+        Self {
+            arms: actions
+                .into_iter()
+                .map(|action| Arm {
+                    action,
+                    visits: 0,
+                    total: 0.0,
+                    children: HashMap::new(),
+                })
+                .collect(),
+        }
+    }
+
+    /// selects an untried arm or the arm with the highest upper confidence bound
+    fn select(&self, exploration: f64) -> usize {
+        if let Some(untried) = self.arms.iter().position(|arm| arm.visits == 0) {
+            return untried;
+        }
+        let ln_visits = (self.arms.iter().map(|arm| arm.visits).sum::<u32>() as f64).ln();
+        let ucb = |arm: &Arm| {
+            arm.total / arm.visits as f64 + exploration * (ln_visits / arm.visits as f64).sqrt()
+        };
+        (0..self.arms.len())
+            .max_by(|a, b| ucb(&self.arms[*a]).total_cmp(&ucb(&self.arms[*b])))
+            .unwrap()
+    }
+}
+
+/// plays the decisions of a simulated turn by walking down the tree
+/// after the first new node the rollout policy takes over
+struct Walker<'a> {
+    exploration: f64,
+    rollout: &'a mut dyn Player,
+    tree: Vec<Node>,
+    path: Vec<(usize, usize)>,
+    in_tree: bool,
+}
+
+impl Walker<'_> {
+    /// returns None if the decision is left to the rollout policy
+    fn decide(
+        &mut self,
+        observation: Observation,
+        actions: impl FnOnce() -> Vec<Action>,
+    ) -> Option<Action> {
+        if !self.in_tree {
+            return None;
+        }
+        let (node, arm) = *self.path.last().unwrap();
+        let child = match self.tree[node].arms[arm].children.get(&observation) {
+            Some(child) => *child,
+            None => {
+                let child = self.tree.len();
+                self.tree.push(Node::new(actions()));
+                self.tree[node].arms[arm]
+                    .children
+                    .insert(observation, child);
+                self.in_tree = false;
+                child
+            }
+        };
+        let arm = self.tree[child].select(self.exploration);
+        self.path.push((child, arm));
+        Some(self.tree[child].arms[arm].action.clone())
+    }
+}
+
+impl Player for Walker<'_> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        match self.decide(Observation::roll(turn), || take_actions(turn)) {
+            Some(action) => action.to_move(turn),
+            None => self.rollout.make_move(state, turn, rng),
+        }
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        match self.decide(Observation::Tutto, draw_actions) {
+            Some(Action::Draw(new_card)) => new_card,
+            Some(Action::Take { .. }) => unreachable!(),
+            None => self.rollout.card_strat(state, last_turn, rng),
+        }
+    }
+}
+
+/// cheap default rollout policy
+/// takes everything, writes a card worth 300 points with at most two dice left
+/// and draws a new card below 1000 points
+struct Greedy;
+
+impl Player for Greedy {
+    fn make_move(&mut self, _state: &GameState, turn: &Turn, _rng: &mut MyRng) -> Move {
+        let takes = turn.categorize_roll();
+        let dice = turn.dice_of(&takes);
+        let dice_left = turn.dice_left_after(&dice);
+        let write = match turn.card {
+            Flush | Clover => false,
+            _ => {
+                let points = turn.this_card_points() + dice.iter().map(|x| x.points()).sum::<i32>();
+                dice_left != 0 && dice_left <= 2 && points >= 300
+            }
+        };
+        Move { takes, write }
+    }
+
+    fn card_strat(&mut self, _state: &GameState, last_turn: &Turn, _rng: &mut MyRng) -> bool {
+        last_turn.previous_cards_total < 1000
+    }
+}
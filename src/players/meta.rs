@@ -0,0 +1,111 @@
+use rand::distributions::{Distribution, WeightedIndex};
+
+use crate::{Card, GameResult, GameState, Move, MyRng, Player, Turn, TurnSummary};
+
+/// decides which of the players of a MetaPlayer makes a decision
+/// the MetaPlayer panics if an index has no player
+pub enum Selector {
+    /// the player for the open card
+    ByCard(Box<dyn Fn(Card) -> usize>),
+    /// the player for the situation on the scoreboard
+    ByScore(Box<dyn Fn(&GameState) -> usize>),
+    /// every player decides and the most common decision is made
+    /// ties go to the earliest player
+    MajorityVote,
+    /// a random player, with a probability proportional to its weight
+    WeightedRandom(WeightedIndex<f64>),
+}
+
+impl Selector {
+    /// panics if the weights are empty, negative or all zero
+    pub fn weighted_random(weights: &[f64]) -> Self {
+        Self::WeightedRandom(WeightedIndex::new(weights).expect("invalid weights"))
+    }
+}
+
+/// combines existing players into a hybrid strategy
+pub struct MetaPlayer {
+    players: Vec<Box<dyn Player>>,
+    selector: Selector,
+}
+
+impl MetaPlayer {
+    pub fn new(players: Vec<Box<dyn Player>>, selector: Selector) -> Self {
+        assert!(!players.is_empty(), "a MetaPlayer needs players");
+        Self { players, selector }
+    }
+
+    /// the index of the deciding player, None for a vote
+    fn select(&self, state: &GameState, card: Card, rng: &mut MyRng) -> Option<usize> {
+        let idx = match &self.selector {
+            Selector::ByCard(select) => select(card),
+            Selector::ByScore(select) => select(state),
+            Selector::MajorityVote => return None,
+            Selector::WeightedRandom(weights) => weights.sample(rng),
+        };
+        assert!(
+            idx < self.players.len(),
+            "the selector chose player {idx} of {}",
+            self.players.len()
+        );
+        Some(idx)
+    }
+}
+
+impl Player for MetaPlayer {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        if let Some(idx) = self.select(state, turn.card, rng) {
+            return self.players[idx].make_move(state, turn, rng);
+        }
+        let moves: Vec<_> = self
+            .players
+            .iter_mut()
+            .map(|player| player.make_move(state, turn, rng))
+            .collect();
+        let key = |m: &Move| (turn.dice_of(&m.takes), m.write);
+        let votes = |m: &Move| moves.iter().filter(|other| key(other) == key(m)).count();
+        let mut best = 0;
+        for (i, m) in moves.iter().enumerate() {
+            if votes(m) > votes(&moves[best]) {
+                best = i;
+            }
+        }
+        moves[best].clone()
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        if let Some(idx) = self.select(state, last_turn.card, rng) {
+            return self.players[idx].card_strat(state, last_turn, rng);
+        }
+        let votes: Vec<_> = self
+            .players
+            .iter_mut()
+            .map(|player| player.card_strat(state, last_turn, rng))
+            .collect();
+        let yes = votes.iter().filter(|v| **v).count();
+        let no = votes.len() - yes;
+        if yes == no {
+            votes[0]
+        } else {
+            yes > no
+        }
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        for player in self.players.iter_mut() {
+            player.on_game_start(seat, n_players);
+        }
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        for player in self.players.iter_mut() {
+            player.on_turn_end(summary);
+        }
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        for player in self.players.iter_mut() {
+            player.on_game_end(result);
+        }
+    }
+}
@@ -1,17 +1,17 @@
 #![allow(unused_variables)]
-use crate::{Game, Move, MyRng, Player, Turn};
+use crate::{GameState, Move, MyRng, Player, Turn};
 
 pub struct NaivePlayer;
 
 impl Player for NaivePlayer {
-    fn make_move(&self, state: &Game, turn: &Turn, rng: &mut MyRng) -> Move {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
         Move {
             takes: turn.categorize_roll(),
             write: false,
         }
     }
 
-    fn card_strat(&self, state: &Game, last_turn: &Turn, rng: &mut MyRng) -> bool {
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
         true
     }
 }
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use crate::{
+    features::{encode_card, encode_move},
+    training::{
+        neural::{legal_outputs, Network},
+        MoveAction,
+    },
+    GameState, Move, MyRng, Player, Turn,
+};
+
+/// plays the most likely action of a trained network
+pub struct NeuralPlayer {
+    network: Network,
+}
+
+impl NeuralPlayer {
+    pub fn new(network: Network) -> Self {
+        Self { network }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self::new(Network::load(path)?))
+    }
+
+    fn best_output(&self, features: &[f32], card_decision: bool) -> usize {
+        let (logits, _) = self.network.forward(features);
+        legal_outputs(card_decision)
+            .max_by(|a, b| logits[*a].total_cmp(&logits[*b]))
+            .unwrap()
+    }
+}
+
+impl Player for NeuralPlayer {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, _rng: &mut MyRng) -> Move {
+        MoveAction::ALL[self.best_output(&encode_move(state, turn), false)].to_move(turn)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, _rng: &mut MyRng) -> bool {
+        self.best_output(&encode_card(state, last_turn), true) == 4
+    }
+}
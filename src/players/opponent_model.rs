@@ -0,0 +1,172 @@
+use super::Threshold;
+use crate::{GameResult, GameState, Move, MyRng, Player, Turn, TurnEnd, TurnSummary};
+
+/// what the turns of one player revealed during the game
+#[derive(Debug, Clone, Default)]
+pub struct OpponentProfile {
+    pub turns: usize,
+    /// the points at risk and the dice left whenever the player wrote with dice left
+    pub writes: Vec<(i32, usize)>,
+    /// new cards drawn after a tutto
+    pub draws: usize,
+    /// tuttos after which no new card was drawn
+    pub passes: usize,
+    /// the points at risk lost to invalid rolls
+    pub busts: Vec<i32>,
+}
+
+impl OpponentProfile {
+    pub fn observe(&mut self, summary: &TurnSummary) {
+        self.turns += 1;
+        // every card after the first was drawn after a tutto
+        self.draws += summary.cards.len().saturating_sub(1);
+        match summary.end {
+            TurnEnd::Wrote {
+                points, dice_left, ..
+            } => self.writes.push((points, dice_left)),
+            TurnEnd::Passed { .. } => self.passes += 1,
+            TurnEnd::Busted { points, .. } => self.busts.push(points),
+            TurnEnd::Stopped | TurnEnd::Finished { .. } => (),
+        }
+    }
+
+    /// the mean points at risk the player wrote at, None before the first write
+    pub fn stop_threshold(&self) -> Option<f64> {
+        mean(self.writes.iter().map(|(points, _)| *points as f64))
+    }
+
+    /// the mean number of dice the player refused to roll, None before the first write
+    pub fn stop_dice_left(&self) -> Option<f64> {
+        mean(self.writes.iter().map(|(_, dice)| *dice as f64))
+    }
+
+    /// the share of tuttos followed by a new card, None before the first tutto
+    pub fn draw_rate(&self) -> Option<f64> {
+        let decisions = self.draws + self.passes;
+        (decisions > 0).then(|| self.draws as f64 / decisions as f64)
+    }
+
+    /// from 0 for a player writing small points and never drawing
+    /// to 1 for a player waiting for 1000 points and always drawing
+    /// None while nothing is known
+    pub fn aggression(&self) -> Option<f64> {
+        let parts: Vec<_> = [
+            self.stop_threshold()
+                .map(|points| (points / 1000.0).min(1.0)),
+            self.draw_rate(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        mean(parts.into_iter())
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, n) = values.fold((0.0, 0), |(sum, n), x| (sum + x, n + 1));
+    (n > 0).then(|| sum / n as f64)
+}
+
+/// profiles every player of the current game from the observed turns
+/// the profiles are reset when a new game starts
+#[derive(Debug, Clone, Default)]
+pub struct OpponentModel {
+    seat: usize,
+    profiles: Vec<OpponentProfile>,
+}
+
+impl OpponentModel {
+    pub fn seat(&self) -> usize {
+        self.seat
+    }
+
+    /// the profile of a seat, the own seat is profiled as well
+    pub fn profile(&self, seat: usize) -> &OpponentProfile {
+        &self.profiles[seat]
+    }
+
+    /// the seats of the other players
+    pub fn opponents(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.profiles.len()).filter(|seat| *seat != self.seat)
+    }
+
+    pub fn start(&mut self, seat: usize, n_players: usize) {
+        self.seat = seat;
+        self.profiles = vec![OpponentProfile::default(); n_players];
+    }
+
+    pub fn observe(&mut self, summary: &TurnSummary) {
+        if let Some(profile) = self.profiles.get_mut(summary.player) {
+            profile.observe(summary);
+        }
+    }
+}
+
+/// plays the wrapped player but banks points early
+/// while an aggressive opponent is close to the point goal
+pub struct OpponentAware<P> {
+    pub player: P,
+    pub model: OpponentModel,
+    /// an opponent this close to the point goal is about to win
+    pub close_to_goal: i32,
+    /// opponents more aggressive than this are dangerous
+    pub min_aggression: f64,
+    /// the threshold written at while a dangerous opponent is close
+    pub safe: Threshold,
+}
+
+impl<P: Player> OpponentAware<P> {
+    pub fn new(player: P) -> Self {
+        Self {
+            player,
+            model: OpponentModel::default(),
+            close_to_goal: 2000,
+            min_aggression: 0.6,
+            safe: Threshold::new(300, 3),
+        }
+    }
+
+    /// whether an aggressive opponent is close to the point goal
+    pub fn in_danger(&self, state: &GameState) -> bool {
+        self.model.opponents().any(|seat| {
+            state.scores()[seat] >= state.rules().point_goal - self.close_to_goal
+                && self
+                    .model
+                    .profile(seat)
+                    .aggression()
+                    .is_some_and(|aggression| aggression > self.min_aggression)
+        })
+    }
+}
+
+impl<P: Player> Player for OpponentAware<P> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        let mut this_move = self.player.make_move(state, turn, rng);
+        if !this_move.write && self.in_danger(state) {
+            let dice = turn.dice_of(&this_move.takes);
+            let dice_left = turn.dice_left_after(&dice);
+            this_move.write =
+                dice_left != 0 && self.safe.write(turn.points_after(&dice), dice_left);
+        }
+        this_move
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        let new_card = self.player.card_strat(state, last_turn, rng);
+        new_card && !(self.in_danger(state) && last_turn.previous_cards_total >= self.safe.points)
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        self.model.start(seat, n_players);
+        self.player.on_game_start(seat, n_players)
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        self.model.observe(summary);
+        self.player.on_turn_end(summary)
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.player.on_game_end(result)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{Card::*, GameResult, GameState, Move, MyRng, Player, Turn, TurnSummary};
+
+/// manages the lead around the PlusMinus threat of the opponents
+///
+/// the leader is the target of every PlusMinus and cannot play one itself.
+/// so this player rolls on instead of writing a lead smaller than `margin`
+/// and as leader only draws a new card while stopping cards are rare enough
+pub struct PlusMinusAware<P> {
+    pub player: P,
+    /// a lead below this is pushed further instead of written
+    pub margin: i32,
+    /// the fewest dice left to push a slim lead with
+    /// the default players write with at most 2 dice left
+    pub min_dice: usize,
+    /// the leader draws no card if Stop and PlusMinus are more likely than this
+    pub max_stop_risk: f64,
+}
+
+impl<P: Player> PlusMinusAware<P> {
+    pub fn new(player: P) -> Self {
+        Self {
+            player,
+            margin: 1000,
+            min_dice: 2,
+            max_stop_risk: 0.25,
+        }
+    }
+}
+
+impl<P: Player> Player for PlusMinusAware<P> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        let mut this_move = self.player.make_move(state, turn, rng);
+        if !this_move.write {
+            return this_move;
+        }
+        let me = state.get_player_idx();
+        let dice = turn.dice_of(&this_move.takes);
+        let points = turn.points_after(&dice);
+        let lead = state.lead(me) + points;
+        let slim_lead = lead >= 0 && lead < self.margin;
+        if slim_lead
+            && state.scores()[me] + points < state.rules().point_goal
+            && turn.dice_left_after(&dice) >= self.min_dice
+        {
+            this_move.write = false;
+        }
+        this_move
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        let new_card = self.player.card_strat(state, last_turn, rng);
+        let is_leader = state.highest_score().1.contains(&state.get_player_idx());
+        if !new_card || !is_leader || last_turn.previous_cards_total == 0 {
+            return new_card;
+        }
+        let stop_risk: f64 = state
+            .deck()
+            .next_card_odds()
+            .into_iter()
+            .filter(|(card, _)| [Stop, PlusMinus].contains(card))
+            .map(|(_, p)| p)
+            .sum();
+        stop_risk <= self.max_stop_risk
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        self.player.on_game_start(seat, n_players)
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        self.player.on_turn_end(summary)
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.player.on_game_end(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{analysis::what_if::Situation, ThresholdPlayer};
+
+    /// a roll on a Bonus(300) where taking the 1 leaves 2 dice
+    fn position(scores: &str) -> (GameState, Turn) {
+        let situation: Situation = ron::from_str(&format!(
+            "(card: Bonus(300), taken_dice: [Triple(4)], roll: [1, 2, 3], scores: {scores})"
+        ))
+        .unwrap();
+        situation.position().unwrap().restore()
+    }
+
+    fn writes(player: &mut dyn Player, scores: &str) -> bool {
+        let (state, turn) = position(scores);
+        player
+            .make_move(&state, &turn, &mut MyRng::seed_from_u64(0))
+            .write
+    }
+
+    #[test]
+    fn pushes_a_slim_lead_the_wrapped_player_writes() {
+        assert!(writes(&mut ThresholdPlayer::default(), "[1000, 1200]"));
+        let mut aware = PlusMinusAware::new(ThresholdPlayer::default());
+        assert!(!writes(&mut aware, "[1000, 1200]"));
+        assert!(writes(&mut aware, "[3000, 1200]"));
+        assert!(writes(&mut aware, "[0, 1200]"));
+    }
+}
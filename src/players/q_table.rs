@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use crate::{
+    training::q_learning::{QTable, StateKey},
+    GameState, Move, MyRng, Player, ThresholdPlayer, Turn,
+};
+
+/// plays the greedy policy of a learned QTable
+/// states the table has never seen are left to the default ThresholdPlayer
+pub struct QTablePlayer {
+    table: QTable,
+    fallback: ThresholdPlayer,
+}
+
+impl QTablePlayer {
+    pub fn new(table: QTable) -> Self {
+        Self {
+            table,
+            fallback: ThresholdPlayer::default(),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self::new(QTable::load(path)?))
+    }
+}
+
+impl Player for QTablePlayer {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        match self.table.best_move(&StateKey::for_move(state, turn)) {
+            Some(action) => action.to_move(turn),
+            None => self.fallback.make_move(state, turn, rng),
+        }
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        match self.table.best_card(&StateKey::for_card(state, last_turn)) {
+            Some(new_card) => new_card,
+            None => self.fallback.card_strat(state, last_turn, rng),
+        }
+    }
+}
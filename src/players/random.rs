@@ -0,0 +1,87 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::take_selection::take_subsets;
+use crate::{Card::*, GameResult, GameState, Move, MyRng, Player, Turn, TurnSummary};
+
+/// every legal move of the roll
+/// writing is left out on a Clover where it is ignored
+pub fn legal_moves(turn: &Turn) -> Vec<Move> {
+    let mut moves = Vec::new();
+    for takes in take_subsets(turn) {
+        if turn.card != Clover {
+            moves.push(Move {
+                takes: takes.clone(),
+                write: true,
+            });
+        }
+        moves.push(Move {
+            takes,
+            write: false,
+        });
+    }
+    moves
+}
+
+/// picks uniformly among the legal moves and draws a new card half of the time
+pub struct RandomPlayer;
+
+impl Player for RandomPlayer {
+    fn make_move(&mut self, _state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        legal_moves(turn)
+            .choose(rng)
+            .expect("a roll with valid dice offers takes")
+            .clone()
+    }
+
+    fn card_strat(&mut self, _state: &GameState, _last_turn: &Turn, rng: &mut MyRng) -> bool {
+        rng.gen()
+    }
+}
+
+/// replaces the decisions of the wrapped player with random legal ones
+/// with probability epsilon
+pub struct Noisy<P> {
+    pub player: P,
+    pub epsilon: f64,
+}
+
+impl<P: Player> Noisy<P> {
+    /// panics if epsilon is no probability
+    pub fn new(player: P, epsilon: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&epsilon),
+            "epsilon is a probability, not {epsilon}"
+        );
+        Self { player, epsilon }
+    }
+}
+
+impl<P: Player> Player for Noisy<P> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        if rng.gen_bool(self.epsilon) {
+            RandomPlayer.make_move(state, turn, rng)
+        } else {
+            self.player.make_move(state, turn, rng)
+        }
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        if rng.gen_bool(self.epsilon) {
+            RandomPlayer.card_strat(state, last_turn, rng)
+        } else {
+            self.player.card_strat(state, last_turn, rng)
+        }
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        self.player.on_game_start(seat, n_players)
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        self.player.on_turn_end(summary)
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.player.on_game_end(result)
+    }
+}
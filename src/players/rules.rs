@@ -0,0 +1,601 @@
+use std::{fmt::Display, path::Path};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{Card, Card::*, GameState, Move, MyRng, Player, Turn, NUMBER_OF_DICE};
+
+/// the rules of a RulePlayer as written in a ron file
+///
+/// every rule reads like `when card == FireWork && dice_left <= 2 && points >= 400 => stop`.
+/// the first matching rule decides, `stop` and `continue` decide moves,
+/// `draw` and `pass` decide whether to draw a new card after a tutto.
+/// the player always takes every offered dice.
+///
+/// variables are `card`, `dice` (rolled), `dice_left` (after taking everything),
+/// `points` (of the turn at risk), `card_points`, `turn_points` (of the previous cards),
+/// `score`, `lead` and `to_goal`. cards are compared by `==` and `!=`
+/// where `Bonus` matches every bonus and `Bonus(300)` only one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleSet {
+    pub rules: Vec<String>,
+    /// the move if no rule matches, `stop` or `continue`
+    #[serde(default = "default_move")]
+    pub default_move: String,
+    /// the card decision if no rule matches, `draw` or `pass`
+    #[serde(default = "default_card")]
+    pub default_card: String,
+}
+
+fn default_move() -> String {
+    "continue".to_string()
+}
+
+fn default_card() -> String {
+    "pass".to_string()
+}
+
+#[derive(Debug, Error)]
+pub enum RuleError {
+    #[error("could not read the rules: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse the rule file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("rule {rule} `{text}`: {reason}")]
+    Invalid {
+        /// counted from 1
+        rule: usize,
+        text: String,
+        reason: String,
+    },
+    #[error("default `{0}` is not a valid action here")]
+    Default(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Stop,
+    Continue,
+    Draw,
+    Pass,
+}
+
+impl Action {
+    fn is_move(self) -> bool {
+        matches!(self, Action::Stop | Action::Continue)
+    }
+
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "stop" => Some(Action::Stop),
+            "continue" => Some(Action::Continue),
+            "draw" => Some(Action::Draw),
+            "pass" => Some(Action::Pass),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variable {
+    Card,
+    Dice,
+    DiceLeft,
+    Points,
+    CardPoints,
+    TurnPoints,
+    Score,
+    Lead,
+    ToGoal,
+}
+
+impl Variable {
+    fn parse(word: &str) -> Option<Self> {
+        Some(match word {
+            "card" => Variable::Card,
+            "dice" => Variable::Dice,
+            "dice_left" => Variable::DiceLeft,
+            "points" => Variable::Points,
+            "card_points" => Variable::CardPoints,
+            "turn_points" => Variable::TurnPoints,
+            "score" => Variable::Score,
+            "lead" => Variable::Lead,
+            "to_goal" => Variable::ToGoal,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn parse(word: &str) -> Option<Self> {
+        Some(match word {
+            "==" => Op::Eq,
+            "!=" => Op::Ne,
+            "<" => Op::Lt,
+            "<=" => Op::Le,
+            ">" => Op::Gt,
+            ">=" => Op::Ge,
+            _ => return None,
+        })
+    }
+
+    fn compare(self, a: i32, b: i32) -> bool {
+        match self {
+            Op::Eq => a == b,
+            Op::Ne => a != b,
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CardPattern {
+    AnyBonus,
+    Card(Card),
+}
+
+impl CardPattern {
+    fn matches(self, card: Card) -> bool {
+        match self {
+            CardPattern::AnyBonus => matches!(card, Bonus(_)),
+            CardPattern::Card(c) => c == card,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Condition {
+    Card(bool, CardPattern),
+    Number(Variable, Op, i32),
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    conditions: Vec<Condition>,
+    action: Action,
+}
+
+/// the values the variables have at a decision
+struct Situation {
+    card: Card,
+    dice: i32,
+    dice_left: i32,
+    points: i32,
+    turn_points: i32,
+    score: i32,
+    lead: i32,
+    to_goal: i32,
+}
+
+impl Situation {
+    fn value(&self, variable: Variable) -> i32 {
+        match variable {
+            Variable::Card => unreachable!("cards are not numbers"),
+            Variable::Dice => self.dice,
+            Variable::DiceLeft => self.dice_left,
+            Variable::Points => self.points,
+            Variable::CardPoints => self.points - self.turn_points,
+            Variable::TurnPoints => self.turn_points,
+            Variable::Score => self.score,
+            Variable::Lead => self.lead,
+            Variable::ToGoal => self.to_goal,
+        }
+    }
+}
+
+impl Rule {
+    fn matches(&self, situation: &Situation) -> bool {
+        self.conditions.iter().all(|condition| match condition {
+            Condition::Card(equal, pattern) => pattern.matches(situation.card) == *equal,
+            Condition::Number(variable, op, value) => {
+                op.compare(situation.value(*variable), *value)
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Number(i32),
+    Symbol(String),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{word}"),
+            Token::Number(n) => write!(f, "{n}"),
+            Token::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else if c.is_ascii_digit()
+            || (c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().collect();
+            tokens.push(Token::Number(
+                number
+                    .parse()
+                    .map_err(|_| format!("number {number} is too large"))?,
+            ));
+        } else {
+            let two: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            if ["==", "!=", "<=", ">=", "&&", "=>"].contains(&two.as_str()) {
+                tokens.push(Token::Symbol(two));
+                i += 2;
+            } else if ['<', '>', '(', ')'].contains(&c) {
+                tokens.push(Token::Symbol(c.to_string()));
+                i += 1;
+            } else {
+                return Err(format!("unexpected character `{c}`"));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+type Tokens = std::iter::Peekable<std::vec::IntoIter<Token>>;
+
+/// parses `when <condition> && ... => <action>`
+fn parse_rule(text: &str) -> Result<Rule, String> {
+    let tokens = tokenize(text)?;
+    let mut tokens = tokens.into_iter().peekable();
+    if tokens.next() != Some(Token::Word("when".to_string())) {
+        return Err("a rule starts with `when`".to_string());
+    }
+    let mut conditions = Vec::new();
+    loop {
+        conditions.push(parse_condition(&mut tokens)?);
+        match tokens.next() {
+            Some(Token::Symbol(s)) if s == "&&" => continue,
+            Some(Token::Symbol(s)) if s == "=>" => break,
+            Some(other) => return Err(format!("expected `&&` or `=>` but found `{other}`")),
+            None => return Err("missing `=> <action>`".to_string()),
+        }
+    }
+    let action = match tokens.next() {
+        Some(Token::Word(word)) => Action::parse(&word)
+            .ok_or_else(|| format!("unknown action `{word}`, use stop, continue, draw or pass"))?,
+        Some(other) => return Err(format!("expected an action but found `{other}`")),
+        None => return Err("missing action after `=>`".to_string()),
+    };
+    if let Some(extra) = tokens.next() {
+        return Err(format!("unexpected `{extra}` after the action"));
+    }
+    Ok(Rule { conditions, action })
+}
+
+fn parse_condition(tokens: &mut Tokens) -> Result<Condition, String> {
+    let variable = match tokens.next() {
+        Some(Token::Word(word)) => {
+            Variable::parse(&word).ok_or_else(|| format!("unknown variable `{word}`"))?
+        }
+        Some(other) => return Err(format!("expected a variable but found `{other}`")),
+        None => return Err("missing condition".to_string()),
+    };
+    let op = match tokens.next() {
+        Some(Token::Symbol(symbol)) => Op::parse(&symbol)
+            .ok_or_else(|| format!("expected a comparison but found `{symbol}`"))?,
+        Some(other) => return Err(format!("expected a comparison but found `{other}`")),
+        None => return Err("missing comparison".to_string()),
+    };
+    if variable == Variable::Card {
+        let equal = match op {
+            Op::Eq => true,
+            Op::Ne => false,
+            _ => return Err("cards can only be compared with `==` and `!=`".to_string()),
+        };
+        return Ok(Condition::Card(equal, parse_card(tokens)?));
+    }
+    match tokens.next() {
+        Some(Token::Number(n)) => Ok(Condition::Number(variable, op, n)),
+        Some(other) => Err(format!("expected a number but found `{other}`")),
+        None => Err("missing number".to_string()),
+    }
+}
+
+fn parse_card(tokens: &mut Tokens) -> Result<CardPattern, String> {
+    let Some(Token::Word(name)) = tokens.next() else {
+        return Err("expected a card name".to_string());
+    };
+    Ok(CardPattern::Card(match name.as_str() {
+        "Bonus" => {
+            if tokens.peek() != Some(&Token::Symbol("(".to_string())) {
+                return Ok(CardPattern::AnyBonus);
+            }
+            return match (tokens.next(), tokens.next(), tokens.next()) {
+                (Some(Token::Symbol(open)), Some(Token::Number(n)), Some(Token::Symbol(close)))
+                    if open == "(" && close == ")" =>
+                {
+                    Ok(CardPattern::Card(Bonus(n)))
+                }
+                _ => Err("write a bonus as `Bonus` or `Bonus(300)`".to_string()),
+            };
+        }
+        "Double" => Double,
+        "FireWork" => FireWork,
+        "Flush" => Flush,
+        "Clover" => Clover,
+        "Stop" => Stop,
+        "PlusMinus" => PlusMinus,
+        _ => return Err(format!("unknown card `{name}`")),
+    }))
+}
+
+/// plays a RuleSet
+pub struct RulePlayer {
+    rules: Vec<Rule>,
+    default_move: Action,
+    default_card: Action,
+}
+
+impl RulePlayer {
+    /// validates the rules
+    pub fn new(rule_set: &RuleSet) -> Result<Self, RuleError> {
+        let rules = rule_set
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(rule, text)| {
+                parse_rule(text).map_err(|reason| RuleError::Invalid {
+                    rule: rule + 1,
+                    text: text.clone(),
+                    reason,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let default_move = Action::parse(&rule_set.default_move)
+            .filter(|action| action.is_move())
+            .ok_or_else(|| RuleError::Default(rule_set.default_move.clone()))?;
+        let default_card = Action::parse(&rule_set.default_card)
+            .filter(|action| !action.is_move())
+            .ok_or_else(|| RuleError::Default(rule_set.default_card.clone()))?;
+        Ok(Self {
+            rules,
+            default_move,
+            default_card,
+        })
+    }
+
+    /// reads and validates a ron file containing a RuleSet
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RuleError> {
+        let rule_set: RuleSet = ron::from_str(&std::fs::read_to_string(path)?)?;
+        Self::new(&rule_set)
+    }
+
+    fn decide(&self, situation: &Situation, moves: bool, default: Action) -> Action {
+        self.rules
+            .iter()
+            .filter(|rule| rule.action.is_move() == moves)
+            .find(|rule| rule.matches(situation))
+            .map(|rule| rule.action)
+            .unwrap_or(default)
+    }
+}
+
+impl Player for RulePlayer {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, _rng: &mut MyRng) -> Move {
+        let takes = turn.categorize_roll();
+        let dice = turn.dice_of(&takes);
+        let me = state.get_player_idx();
+        let situation = Situation {
+            card: turn.card,
+            dice: turn.roll.len() as i32,
+            dice_left: turn.dice_left_after(&dice) as i32,
+            points: turn.points_after(&dice),
+            turn_points: turn.previous_cards_total,
+            score: state.scores()[me],
+            lead: state.lead(me),
+            to_goal: state.rules().point_goal - state.scores()[me],
+        };
+        let stop = self.decide(&situation, true, self.default_move) == Action::Stop;
+        Move {
+            takes,
+            write: stop && situation.dice_left != 0,
+        }
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, _rng: &mut MyRng) -> bool {
+        let me = state.get_player_idx();
+        let situation = Situation {
+            card: last_turn.card,
+            dice: NUMBER_OF_DICE as i32,
+            dice_left: NUMBER_OF_DICE as i32,
+            points: last_turn.previous_cards_total,
+            turn_points: last_turn.previous_cards_total,
+            score: state.scores()[me],
+            lead: state.lead(me),
+            to_goal: state.rules().point_goal - state.scores()[me],
+        };
+        self.decide(&situation, false, self.default_card) == Action::Draw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn situation(card: Card, dice_left: i32, points: i32) -> Situation {
+        Situation {
+            card,
+            dice: dice_left,
+            dice_left,
+            points,
+            turn_points: 0,
+            score: 0,
+            lead: -600,
+            to_goal: 10_000,
+        }
+    }
+
+    fn rule_set(rules: &[&str]) -> RuleSet {
+        RuleSet {
+            rules: rules.iter().map(|rule| rule.to_string()).collect(),
+            default_move: default_move(),
+            default_card: default_card(),
+        }
+    }
+
+    #[test]
+    fn the_example_rule_stops_on_a_fire_work() {
+        let rule =
+            parse_rule("when card == FireWork && dice_left <= 2 && points >= 400 => stop").unwrap();
+        assert_eq!(rule.action, Action::Stop);
+        assert!(rule.matches(&situation(FireWork, 2, 400)));
+        assert!(!rule.matches(&situation(FireWork, 3, 400)));
+        assert!(!rule.matches(&situation(FireWork, 2, 350)));
+        assert!(!rule.matches(&situation(Double, 2, 400)));
+    }
+
+    #[test]
+    fn bonus_matches_every_bonus_and_bonus_300_one() {
+        let any = parse_rule("when card == Bonus => stop").unwrap();
+        let one = parse_rule("when card == Bonus(300) => stop").unwrap();
+        let not_one = parse_rule("when card != Bonus(300) => stop").unwrap();
+        for card in [Bonus(200), Bonus(300)] {
+            assert!(any.matches(&situation(card, 2, 0)));
+        }
+        assert!(!any.matches(&situation(Double, 2, 0)));
+        assert!(one.matches(&situation(Bonus(300), 2, 0)));
+        assert!(!one.matches(&situation(Bonus(200), 2, 0)));
+        assert!(!not_one.matches(&situation(Bonus(300), 2, 0)));
+        assert!(not_one.matches(&situation(Bonus(200), 2, 0)));
+    }
+
+    #[test]
+    fn numbers_can_be_negative() {
+        for text in [
+            "when lead < -500 => continue",
+            "when lead <-500 => continue",
+        ] {
+            let rule = parse_rule(text).unwrap();
+            assert!(matches!(
+                rule.conditions[..],
+                [Condition::Number(Variable::Lead, Op::Lt, -500)]
+            ));
+            assert!(rule.matches(&situation(Double, 2, 0)));
+        }
+    }
+
+    #[test]
+    fn errors_name_the_rule_and_the_reason() {
+        let cases = [
+            ("when points >= 300 $ => stop", "unexpected character `$`"),
+            (
+                "when points >= 99999999999 => stop",
+                "number 99999999999 is too large",
+            ),
+            ("if points >= 300 => stop", "a rule starts with `when`"),
+            (
+                "when points >= 300 stop",
+                "expected `&&` or `=>` but found `stop`",
+            ),
+            ("when points >= 300", "missing `=> <action>`"),
+            (
+                "when points >= 300 => write",
+                "unknown action `write`, use stop, continue, draw or pass",
+            ),
+            (
+                "when points >= 300 => 5",
+                "expected an action but found `5`",
+            ),
+            ("when points >= 300 =>", "missing action after `=>`"),
+            (
+                "when points >= 300 => stop now",
+                "unexpected `now` after the action",
+            ),
+            (
+                "when score_left >= 300 => stop",
+                "unknown variable `score_left`",
+            ),
+            (
+                "when 300 <= points => stop",
+                "expected a variable but found `300`",
+            ),
+            ("when", "missing condition"),
+            (
+                "when points && => stop",
+                "expected a comparison but found `&&`",
+            ),
+            (
+                "when points 300 => stop",
+                "expected a comparison but found `300`",
+            ),
+            ("when points", "missing comparison"),
+            (
+                "when card < Double => stop",
+                "cards can only be compared with `==` and `!=`",
+            ),
+            (
+                "when points >= many => stop",
+                "expected a number but found `many`",
+            ),
+            ("when points >=", "missing number"),
+            ("when card == 300 => stop", "expected a card name"),
+            (
+                "when card == Bonus(x) => stop",
+                "write a bonus as `Bonus` or `Bonus(300)`",
+            ),
+            ("when card == Joker => stop", "unknown card `Joker`"),
+        ];
+        for (text, reason) in cases {
+            let error = RulePlayer::new(&rule_set(&["when dice == 6 => continue", text]))
+                .err()
+                .unwrap();
+            assert_eq!(error.to_string(), format!("rule 2 `{text}`: {reason}"));
+        }
+    }
+
+    #[test]
+    fn defaults_need_an_action_of_their_kind() {
+        let mut rules = rule_set(&[]);
+        rules.default_move = "draw".to_string();
+        let error = RulePlayer::new(&rules).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "default `draw` is not a valid action here"
+        );
+
+        let mut rules = rule_set(&[]);
+        rules.default_card = "stop".to_string();
+        let error = RulePlayer::new(&rules).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "default `stop` is not a valid action here"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use super::{split::Aggressive, split::CardStrategy, Threshold, ThresholdParams};
+use crate::{Card::*, GameState, Move, MyRng, Player, Turn};
+
+/// turns the scoreboard into a risk appetite
+/// a risk of 1 is neutral, larger values mean playing for more points
+#[derive(Debug, Clone)]
+pub struct ScoreAwareness {
+    /// risk added per thousand points behind the leader
+    pub per_thousand_behind: f64,
+    /// an opponent this close to the point goal is about to win
+    pub close_to_goal: i32,
+    /// risk added per opponent about to win
+    pub per_close_opponent: f64,
+    /// risk removed while being a target of the opponents' PlusMinus
+    pub target_caution: f64,
+    pub min_risk: f64,
+    pub max_risk: f64,
+}
+
+impl Default for ScoreAwareness {
+    fn default() -> Self {
+        Self {
+            per_thousand_behind: 0.1,
+            close_to_goal: 2000,
+            per_close_opponent: 0.3,
+            target_caution: 0.2,
+            min_risk: 0.5,
+            max_risk: 3.0,
+        }
+    }
+}
+
+impl ScoreAwareness {
+    /// the risk appetite of the current player
+    pub fn risk(&self, state: &GameState) -> f64 {
+        let me = state.get_player_idx();
+        let (highest, leaders) = state.highest_score();
+        let my_score = state.scores()[me];
+        let mut risk = 1.0;
+        risk += self.per_thousand_behind * (highest - my_score) as f64 / 1000.0;
+        let close_opponents = state
+            .scores()
+            .iter()
+            .enumerate()
+            .filter(|(i, score)| {
+                *i != me && state.rules().point_goal - **score <= self.close_to_goal
+            })
+            .count();
+        if my_score < highest {
+            risk += self.per_close_opponent * close_opponents as f64;
+        }
+        if leaders.contains(&me) && state.scores().len() > 1 {
+            risk -= self.target_caution;
+        }
+        risk.clamp(self.min_risk, self.max_risk)
+    }
+}
+
+/// a ThresholdPlayer whose thresholds are scaled by the risk appetite
+/// it always writes when the points at risk win the game
+pub struct ScoreAwarePlayer {
+    pub params: ThresholdParams,
+    pub awareness: ScoreAwareness,
+}
+
+impl ScoreAwarePlayer {
+    pub fn new(params: ThresholdParams, awareness: ScoreAwareness) -> Self {
+        Self { params, awareness }
+    }
+}
+
+impl Default for ScoreAwarePlayer {
+    fn default() -> Self {
+        Self::new(ThresholdParams::default(), ScoreAwareness::default())
+    }
+}
+
+impl Player for ScoreAwarePlayer {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        let threshold = match turn.card {
+            Bonus(_) => self.params.bonus,
+            Double => self.params.double,
+            FireWork => self.params.fire_work,
+            PlusMinus => self.params.plus_minus,
+            Flush | Clover | Stop => return Aggressive.make_move(state, turn, rng),
+        };
+        let takes = turn.categorize_roll();
+        let dice = turn.dice_of(&takes);
+        let my_score = state.scores()[state.get_player_idx()];
+        if turn.dice_left_after(&dice) != 0
+            && my_score + turn.points_after(&dice) >= state.rules().point_goal
+        {
+            return Move { takes, write: true };
+        }
+        let risk = self.awareness.risk(state);
+        let mut scaled = Threshold::new(
+            (threshold.points as f64 * risk).min(i32::MAX as f64) as i32,
+            threshold.dice_left,
+        );
+        scaled.make_move(state, turn, rng)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, _rng: &mut MyRng) -> bool {
+        let my_score = state.scores()[state.get_player_idx()];
+        if my_score + last_turn.previous_cards_total >= state.rules().point_goal {
+            return false;
+        }
+        let risk = self.awareness.risk(state);
+        (last_turn.previous_cards_total as f64) < self.params.draw_below as f64 * risk
+    }
+}
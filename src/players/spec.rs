@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use anyhow::ensure;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    CardCountingPlayer, ClonedPlayer, MctsPlayer, NeuralPlayer, Noisy, OpponentAware,
+    PlusMinusAware, QTablePlayer, RandomPlayer, RulePlayer, RuleSet, ScoreAwarePlayer,
+    ThresholdParams, ThresholdPlayer,
+};
+use crate::{NaivePlayer, Player};
+
+/// a player described in a ron file, files are read relative to the working directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PlayerSpec {
+    Naive,
+    Random,
+    Threshold(ThresholdParams),
+    /// a ThresholdParams file, e.g. the best.ron written by the evolution
+    ThresholdFile(String),
+    ScoreAware,
+    Mcts {
+        iterations: usize,
+        exploration: f64,
+    },
+    QTable(String),
+    Neural(String),
+    Rules(RuleSet),
+    /// a RuleSet file
+    RulesFile(String),
+    /// a ClonedPolicy file fitted to recorded decisions
+    Cloned(String),
+    CardCounting(Box<PlayerSpec>),
+    PlusMinusAware(Box<PlayerSpec>),
+    OpponentAware(Box<PlayerSpec>),
+    Noisy(Box<PlayerSpec>, f64),
+}
+
+impl PlayerSpec {
+    /// reads a spec from a ron file
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(ron::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn build(&self) -> anyhow::Result<Box<dyn Player>> {
+        Ok(match self {
+            PlayerSpec::Naive => Box::new(NaivePlayer),
+            PlayerSpec::Random => Box::new(RandomPlayer),
+            PlayerSpec::Threshold(params) => Box::new(ThresholdPlayer::new(params.clone())),
+            PlayerSpec::ThresholdFile(path) => Box::new(ThresholdPlayer::load(path)?),
+            PlayerSpec::ScoreAware => Box::new(ScoreAwarePlayer::default()),
+            PlayerSpec::Mcts {
+                iterations,
+                exploration,
+            } => Box::new(MctsPlayer::new(*iterations, *exploration)),
+            PlayerSpec::QTable(path) => Box::new(QTablePlayer::load(path)?),
+            PlayerSpec::Neural(path) => Box::new(NeuralPlayer::load(path)?),
+            PlayerSpec::Rules(rule_set) => Box::new(RulePlayer::new(rule_set)?),
+            PlayerSpec::RulesFile(path) => Box::new(RulePlayer::load(path)?),
+            PlayerSpec::Cloned(path) => Box::new(ClonedPlayer::load(path)?),
+            PlayerSpec::CardCounting(spec) => Box::new(CardCountingPlayer::new(spec.build()?)),
+            PlayerSpec::PlusMinusAware(spec) => Box::new(PlusMinusAware::new(spec.build()?)),
+            PlayerSpec::OpponentAware(spec) => Box::new(OpponentAware::new(spec.build()?)),
+            PlayerSpec::Noisy(spec, epsilon) => {
+                ensure!(
+                    (0.0..=1.0).contains(epsilon),
+                    "the epsilon of a Noisy player is a probability, not {epsilon}"
+                );
+                Box::new(Noisy::new(spec.build()?, *epsilon))
+            }
+        })
+    }
+}
@@ -0,0 +1,164 @@
+use super::{SplitPlayer, Threshold};
+use crate::{GameState, Move, MyRng, Turn};
+
+/// decides the moves while one kind of card is open
+pub trait CardStrategy {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move;
+}
+
+/// decides whether to draw a new card after a tutto
+pub trait DrawStrategy {
+    fn draw(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool;
+}
+
+/// takes everything and never writes, the card ends with a tutto or nothing
+pub struct Aggressive;
+
+impl CardStrategy for Aggressive {
+    fn make_move(&mut self, _state: &GameState, turn: &Turn, _rng: &mut MyRng) -> Move {
+        Move {
+            takes: turn.categorize_roll(),
+            write: false,
+        }
+    }
+}
+
+/// takes everything and writes as soon as 200 points are at risk with at most three dice left
+pub struct Cautious;
+
+impl CardStrategy for Cautious {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        Threshold::new(200, 3).make_move(state, turn, rng)
+    }
+}
+
+/// takes everything and writes once the threshold is reached
+impl CardStrategy for Threshold {
+    fn make_move(&mut self, _state: &GameState, turn: &Turn, _rng: &mut MyRng) -> Move {
+        let takes = turn.categorize_roll();
+        let dice = turn.dice_of(&takes);
+        let dice_left = turn.dice_left_after(&dice);
+        let write = dice_left != 0 && self.write(turn.points_after(&dice), dice_left);
+        Move { takes, write }
+    }
+}
+
+/// draws a new card while the points of the turn are below the limit
+pub struct DrawBelow(pub i32);
+
+impl DrawStrategy for DrawBelow {
+    fn draw(&mut self, _state: &GameState, last_turn: &Turn, _rng: &mut MyRng) -> bool {
+        last_turn.previous_cards_total < self.0
+    }
+}
+
+/// a SplitPlayer assembled from one component per card
+/// use SplitBuilder to create it
+pub struct SplitStrategy {
+    bonus: Box<dyn CardStrategy>,
+    double: Box<dyn CardStrategy>,
+    fire_work: Box<dyn CardStrategy>,
+    flush: Box<dyn CardStrategy>,
+    clover: Box<dyn CardStrategy>,
+    plus_minus: Box<dyn CardStrategy>,
+    draw: Box<dyn DrawStrategy>,
+}
+
+impl SplitPlayer for SplitStrategy {
+    fn tutto_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.clover.make_move(state, turn, rng)
+    }
+
+    fn bonus_strat(&mut self, _num: i32, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.bonus.make_move(state, turn, rng)
+    }
+
+    fn double_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.double.make_move(state, turn, rng)
+    }
+
+    fn fire_work_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.fire_work.make_move(state, turn, rng)
+    }
+
+    fn flush_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.flush.make_move(state, turn, rng)
+    }
+
+    fn plus_minus_strat(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        self.plus_minus.make_move(state, turn, rng)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        self.draw.draw(state, last_turn, rng)
+    }
+}
+
+/// mixes components into a SplitStrategy
+/// every card starts out with the default ThresholdPlayer behaviour
+/// so `SplitBuilder::new().fire_work(Aggressive).double(Cautious).build()` only changes two cards
+pub struct SplitBuilder {
+    strategy: SplitStrategy,
+}
+
+impl SplitBuilder {
+    pub fn new() -> Self {
+        let params = super::ThresholdParams::default();
+        Self {
+            strategy: SplitStrategy {
+                bonus: Box::new(params.bonus),
+                double: Box::new(params.double),
+                fire_work: Box::new(params.fire_work),
+                flush: Box::new(Aggressive),
+                clover: Box::new(Aggressive),
+                plus_minus: Box::new(params.plus_minus),
+                draw: Box::new(DrawBelow(params.draw_below)),
+            },
+        }
+    }
+
+    pub fn bonus(mut self, strategy: impl CardStrategy + 'static) -> Self {
+        self.strategy.bonus = Box::new(strategy);
+        self
+    }
+
+    pub fn double(mut self, strategy: impl CardStrategy + 'static) -> Self {
+        self.strategy.double = Box::new(strategy);
+        self
+    }
+
+    pub fn fire_work(mut self, strategy: impl CardStrategy + 'static) -> Self {
+        self.strategy.fire_work = Box::new(strategy);
+        self
+    }
+
+    pub fn flush(mut self, strategy: impl CardStrategy + 'static) -> Self {
+        self.strategy.flush = Box::new(strategy);
+        self
+    }
+
+    pub fn clover(mut self, strategy: impl CardStrategy + 'static) -> Self {
+        self.strategy.clover = Box::new(strategy);
+        self
+    }
+
+    pub fn plus_minus(mut self, strategy: impl CardStrategy + 'static) -> Self {
+        self.strategy.plus_minus = Box::new(strategy);
+        self
+    }
+
+    pub fn draw(mut self, strategy: impl DrawStrategy + 'static) -> Self {
+        self.strategy.draw = Box::new(strategy);
+        self
+    }
+
+    pub fn build(self) -> SplitStrategy {
+        self.strategy
+    }
+}
+
+impl Default for SplitBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
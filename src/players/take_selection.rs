@@ -0,0 +1,148 @@
+use crate::{GameResult, GameState, Move, MyRng, Player, Take, Turn, TurnSummary, NUMBER_OF_DICE};
+
+/// all distinct non-empty subsets of the takes offered by the roll
+/// subsets setting aside the same dice are only listed once
+pub fn take_subsets(turn: &Turn) -> Vec<Vec<Take>> {
+    let takes = turn.categorize_roll();
+    let mut subsets = Vec::new();
+    let mut seen = Vec::new();
+    for mask in 1..(1_u32 << takes.len()) {
+        let subset: Vec<_> = takes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, take)| *take)
+            .collect();
+        let dice = turn.dice_of(&subset);
+        if !seen.contains(&dice) {
+            seen.push(dice);
+            subsets.push(subset);
+        }
+    }
+    subsets
+}
+
+/// values setting aside some takes and rolling on
+pub trait TakeEvaluator {
+    fn evaluate(&mut self, state: &GameState, turn: &Turn, takes: &[Take], rng: &mut MyRng) -> f64;
+}
+
+/// returns the subset of takes with the highest value
+pub fn select_takes(
+    evaluator: &mut dyn TakeEvaluator,
+    state: &GameState,
+    turn: &Turn,
+    rng: &mut MyRng,
+) -> Vec<Take> {
+    take_subsets(turn)
+        .into_iter()
+        .map(|takes| (evaluator.evaluate(state, turn, &takes, rng), takes))
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, takes)| takes)
+        .expect("a roll with valid dice offers takes")
+}
+
+/// the points of the takes plus a fixed value for the dice left to roll
+pub struct Heuristic {
+    /// the value of rolling on with the index as number of dice, index 0 is a tutto
+    pub dice_values: [f64; NUMBER_OF_DICE + 1],
+}
+
+impl Default for Heuristic {
+    fn default() -> Self {
+        Self {
+            dice_values: [500.0, 25.0, 50.0, 100.0, 175.0, 250.0, 350.0],
+        }
+    }
+}
+
+impl TakeEvaluator for Heuristic {
+    fn evaluate(
+        &mut self,
+        _state: &GameState,
+        turn: &Turn,
+        takes: &[Take],
+        _rng: &mut MyRng,
+    ) -> f64 {
+        let dice = turn.dice_of(takes);
+        (turn.points_after(&dice) - turn.points_after(&[])) as f64
+            + self.dice_values[turn.dice_left_after(&dice)]
+    }
+}
+
+/// the mean change of the lead over the best opponent when the rest of the turn is played
+/// by the policy after setting the takes aside
+/// the hidden order of the deck is resampled for every sample
+pub struct Rollout {
+    policy: Box<dyn Player>,
+    samples: usize,
+}
+
+impl Rollout {
+    pub fn new(policy: impl Player + 'static, samples: usize) -> Self {
+        Self {
+            policy: Box::new(policy),
+            samples,
+        }
+    }
+}
+
+impl TakeEvaluator for Rollout {
+    fn evaluate(&mut self, state: &GameState, turn: &Turn, takes: &[Take], rng: &mut MyRng) -> f64 {
+        let me = state.get_player_idx();
+        let before = state.lead(me);
+        let mut total = 0;
+        for _ in 0..self.samples {
+            let mut sim = state.determinize(rng);
+            let this_move = Move {
+                takes: takes.to_vec(),
+                write: false,
+            };
+            sim.continue_turn_with_move(self.policy.as_mut(), turn.clone(), this_move, rng);
+            total += sim.lead(me) - before;
+        }
+        total as f64 / self.samples.max(1) as f64
+    }
+}
+
+/// lets the wrapped player decide when to write
+/// and the evaluator which dice to set aside when rolling on
+pub struct TakeSelecting<P, E> {
+    pub player: P,
+    pub evaluator: E,
+}
+
+impl<P: Player, E: TakeEvaluator> TakeSelecting<P, E> {
+    pub fn new(player: P, evaluator: E) -> Self {
+        Self { player, evaluator }
+    }
+}
+
+impl<P: Player, E: TakeEvaluator> Player for TakeSelecting<P, E> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        let this_move = self.player.make_move(state, turn, rng);
+        if this_move.write {
+            return this_move;
+        }
+        Move {
+            takes: select_takes(&mut self.evaluator, state, turn, rng),
+            write: false,
+        }
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        self.player.card_strat(state, last_turn, rng)
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        self.player.on_game_start(seat, n_players)
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        self.player.on_turn_end(summary)
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.player.on_game_end(result)
+    }
+}
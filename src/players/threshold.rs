@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::split::{Aggressive, CardStrategy};
+use crate::{Card::*, GameState, Move, MyRng, Player, Turn};
+
+/// when to write the points of a card
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Threshold {
+    /// the points at risk needed to write
+    pub points: i32,
+    /// the most dice left over with which the player still writes
+    pub dice_left: usize,
+}
+
+impl Threshold {
+    pub fn new(points: i32, dice_left: usize) -> Self {
+        Self { points, dice_left }
+    }
+
+    /// returns true if the points should be written
+    pub fn write(&self, points_at_risk: i32, dice_left: usize) -> bool {
+        dice_left <= self.dice_left && points_at_risk >= self.points
+    }
+}
+
+/// the parameters of the ThresholdPlayer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdParams {
+    pub bonus: Threshold,
+    pub double: Threshold,
+    pub fire_work: Threshold,
+    pub plus_minus: Threshold,
+    /// a new card is drawn while the points of the turn are below this
+    pub draw_below: i32,
+}
+
+impl Default for ThresholdParams {
+    fn default() -> Self {
+        Self {
+            bonus: Threshold::new(300, 2),
+            double: Threshold::new(300, 2),
+            fire_work: Threshold::new(i32::MAX, 0),
+            plus_minus: Threshold::new(i32::MAX, 0),
+            draw_below: 1000,
+        }
+    }
+}
+
+impl ThresholdParams {
+    pub const NUMBER_OF_GENES: usize = 9;
+
+    /// flattens the parameters for optimizers
+    pub fn to_genes(&self) -> Vec<f64> {
+        let mut genes = Vec::with_capacity(Self::NUMBER_OF_GENES);
+        for threshold in [self.bonus, self.double, self.fire_work, self.plus_minus] {
+            genes.push(threshold.points.min(POINTS_CAP) as f64);
+            genes.push(threshold.dice_left as f64);
+        }
+        genes.push(self.draw_below.min(POINTS_CAP) as f64);
+        genes
+    }
+
+    /// inverse of to_genes, out of range genes are clamped
+    pub fn from_genes(genes: &[f64]) -> Self {
+        debug_assert_eq!(genes.len(), Self::NUMBER_OF_GENES);
+        let points = |gene: f64| gene.round().clamp(0.0, POINTS_CAP as f64) as i32;
+        let threshold = |i: usize| {
+            Threshold::new(
+                points(genes[2 * i]),
+                genes[2 * i + 1]
+                    .round()
+                    .clamp(0.0, crate::NUMBER_OF_DICE as f64) as usize,
+            )
+        };
+        Self {
+            bonus: threshold(0),
+            double: threshold(1),
+            fire_work: threshold(2),
+            plus_minus: threshold(3),
+            draw_below: points(genes[8]),
+        }
+    }
+
+    /// the step size of each gene used for mutations
+    pub fn gene_scales() -> Vec<f64> {
+        vec![100.0, 1.0, 100.0, 1.0, 100.0, 1.0, 100.0, 1.0, 200.0]
+    }
+}
+
+/// thresholds above this are as good as never writing
+const POINTS_CAP: i32 = 10_000;
+
+/// takes all dice and writes according to a threshold per card
+/// on Flush and Clover it never writes
+pub struct ThresholdPlayer {
+    pub params: ThresholdParams,
+}
+
+impl ThresholdPlayer {
+    pub fn new(params: ThresholdParams) -> Self {
+        Self { params }
+    }
+
+    /// reads the parameters from a ron file
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let params = ron::from_str(&std::fs::read_to_string(path)?)?;
+        Ok(Self::new(params))
+    }
+}
+
+impl Default for ThresholdPlayer {
+    fn default() -> Self {
+        Self::new(ThresholdParams::default())
+    }
+}
+
+impl Player for ThresholdPlayer {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        match turn.card {
+            Bonus(_) => self.params.bonus.make_move(state, turn, rng),
+            Double => self.params.double.make_move(state, turn, rng),
+            FireWork => self.params.fire_work.make_move(state, turn, rng),
+            PlusMinus => self.params.plus_minus.make_move(state, turn, rng),
+            Flush | Clover | Stop => Aggressive.make_move(state, turn, rng),
+        }
+    }
+
+    fn card_strat(&mut self, _state: &GameState, last_turn: &Turn, _rng: &mut MyRng) -> bool {
+        last_turn.previous_cards_total < self.params.draw_below
+    }
+}
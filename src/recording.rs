@@ -0,0 +1,160 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Card, CardLog, Deck, GameResult, GameState, Move, MyRng, Player, PlayerLog, Rules, TakenDice,
+    Turn, TurnSummary,
+};
+
+/// everything needed to continue a game from a decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    pub turn_number: usize,
+    pub scores: Vec<i32>,
+    pub logs: Vec<PlayerLog>,
+    /// the cards left before the reshuffle
+    pub deck_new: Vec<Card>,
+    /// the cards drawn since the last shuffle, the open card is last
+    pub deck_seen: Vec<Card>,
+    pub card: Card,
+    pub taken_dice: Vec<TakenDice>,
+    pub previous_cards_total: i32,
+    pub fire_work_points: i32,
+    pub clover_win_next_tutto: bool,
+    pub achieved_minus: u32,
+    pub card_logs: Vec<CardLog>,
+    pub roll: Vec<u8>,
+    pub card_is_finished: bool,
+    #[serde(default)]
+    pub rules: Rules,
+}
+
+impl Position {
+    pub fn capture(state: &GameState, turn: &Turn) -> Self {
+        Self {
+            turn_number: state.turn,
+            scores: state.scores.clone(),
+            logs: state.log.clone(),
+            deck_new: state.deck.unseen().to_vec(),
+            deck_seen: state.deck.seen_since_shuffle().to_vec(),
+            card: turn.card,
+            taken_dice: turn.taken_dice.clone(),
+            previous_cards_total: turn.previous_cards_total,
+            fire_work_points: turn.fire_work_points,
+            clover_win_next_tutto: turn.clover_win_next_tutto,
+            achieved_minus: turn.achieved_minus,
+            card_logs: turn.logs.clone(),
+            roll: turn.roll.clone(),
+            card_is_finished: turn.card_is_finished,
+            rules: state.rules.clone(),
+        }
+    }
+
+    /// rebuilds the state and the turn of the decision
+    pub fn restore(&self) -> (GameState, Turn) {
+        let state = GameState {
+            turn: self.turn_number,
+            log: self.logs.clone(),
+            deck: Deck::from_piles(self.deck_new.clone(), self.deck_seen.clone()),
+            scores: self.scores.clone(),
+            rules: self.rules.clone(),
+        };
+        let turn = Turn {
+            card: self.card,
+            taken_dice: self.taken_dice.clone(),
+            previous_cards_total: self.previous_cards_total,
+            fire_work_points: self.fire_work_points,
+            clover_win_next_tutto: self.clover_win_next_tutto,
+            achieved_minus: self.achieved_minus,
+            logs: self.card_logs.clone(),
+            roll: self.roll.clone(),
+            card_is_finished: self.card_is_finished,
+            end: None,
+        };
+        (state, turn)
+    }
+
+    /// the seat of the deciding player
+    pub fn player(&self) -> usize {
+        self.turn_number % self.scores.len()
+    }
+}
+
+/// what was decided in a position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Decision {
+    Move(Move),
+    /// whether a new card was drawn after a tutto
+    Card(bool),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub position: Position,
+    pub decision: Decision,
+}
+
+/// reads a file written by a Recorder
+pub fn load_records(path: impl AsRef<Path>) -> anyhow::Result<Vec<DecisionRecord>> {
+    std::fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(ron::from_str(line)?))
+        .collect()
+}
+
+/// appends every decision of the wrapped player to a file, one ron record per line
+/// the file survives games that are aborted halfway
+pub struct Recorder<P> {
+    pub player: P,
+    file: File,
+}
+
+impl<P: Player> Recorder<P> {
+    pub fn new(player: P, path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { player, file })
+    }
+
+    fn record(&self, state: &GameState, turn: &Turn, decision: Decision) {
+        let record = DecisionRecord {
+            position: Position::capture(state, turn),
+            decision,
+        };
+        let line = ron::to_string(&record).expect("records can be serialized");
+        if let Err(err) = writeln!(&self.file, "{line}") {
+            eprintln!("could not record the decision: {err}");
+        }
+    }
+}
+
+impl<P: Player> Player for Recorder<P> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        let this_move = self.player.make_move(state, turn, rng);
+        self.record(state, turn, Decision::Move(this_move.clone()));
+        this_move
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        let new_card = self.player.card_strat(state, last_turn, rng);
+        self.record(state, last_turn, Decision::Card(new_card));
+        new_card
+    }
+
+    fn on_game_start(&mut self, seat: usize, n_players: usize) {
+        self.player.on_game_start(seat, n_players)
+    }
+
+    fn on_turn_end(&mut self, summary: &TurnSummary) {
+        self.player.on_turn_end(summary)
+    }
+
+    fn on_game_end(&mut self, result: &GameResult) {
+        self.player.on_game_end(result)
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    deck::get_official_cards,
+    make_rng,
+    players::{Player, PlayerSpec},
+    Card, GameResult, GameState, MyRng, Rules, TurnSummary,
+};
+
+/// games taking longer than this are counted as a draw
+pub const MAX_TURNS: usize = 10_000;
+
+/// plays a game silently and returns the index of the winner
+/// returns None if nobody won within MAX_TURNS
+pub fn play_match(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    rng: &mut MyRng,
+) -> Option<usize> {
+    play_observed(players, cards, rng, |_, _| ()).winner
+}
+
+/// plays a game silently calling the lifecycle hooks of the players
+/// `observe` is shown the state and the summary after every turn
+pub fn play_observed(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    rng: &mut MyRng,
+    observe: impl FnMut(&GameState, &TurnSummary),
+) -> GameResult {
+    play_with_rules(players, cards, &Rules::default(), rng, observe)
+}
+
+/// like play_observed with a variant of the rules
+pub fn play_with_rules(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    rules: &Rules,
+    rng: &mut MyRng,
+    mut observe: impl FnMut(&GameState, &TurnSummary),
+) -> GameResult {
+    let n = players.len();
+    let mut state = GameState::with_rules(n, cards.to_vec(), rules.clone(), rng);
+    for (seat, player) in players.iter_mut().enumerate() {
+        player.on_game_start(seat, n);
+    }
+    for _ in 0..MAX_TURNS {
+        let summary = state.next_turn(players[state.get_player_idx()], rng);
+        observe(&state, &summary);
+        for player in players.iter_mut() {
+            player.on_turn_end(&summary);
+        }
+        if state.winner().is_some() {
+            break;
+        }
+    }
+    let result = state.result();
+    for player in players.iter_mut() {
+        player.on_game_end(&result);
+    }
+    result
+}
+
+/// plays the number of games rotating the seats after every game
+/// returns the share of games won by each player
+pub fn win_rates(
+    players: &mut [&mut dyn Player],
+    cards: &[Card],
+    games: usize,
+    rng: &mut MyRng,
+) -> Vec<f64> {
+    let n = players.len();
+    let mut wins = vec![0; n];
+    for game in 0..games {
+        if let Some(seat) = play_match(players, cards, rng) {
+            wins[(seat + game) % n] += 1;
+        }
+        players.rotate_left(1);
+    }
+    players.rotate_right(games % n.max(1));
+    wins.into_iter()
+        .map(|w| w as f64 / games.max(1) as f64)
+        .collect()
+}
+
+/// a tournament described in a ron file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentConfig {
+    pub players: Vec<PlayerSpec>,
+    #[serde(default = "default_games")]
+    pub games: usize,
+    #[serde(default)]
+    pub seed: Option<String>,
+    #[serde(default = "get_official_cards")]
+    pub cards: Vec<Card>,
+}
+
+pub(crate) fn default_games() -> usize {
+    1000
+}
+
+impl TournamentConfig {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(ron::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn build_players(&self) -> anyhow::Result<Vec<Box<dyn Player>>> {
+        self.players.iter().map(PlayerSpec::build).collect()
+    }
+
+    /// builds the players and returns their win rates
+    pub fn run(&self) -> anyhow::Result<Vec<f64>> {
+        let mut players = self.build_players()?;
+        let mut players: Vec<&mut dyn Player> =
+            players.iter_mut().map(|p| p as &mut dyn Player).collect();
+        let mut rng = make_rng(self.seed.as_deref());
+        Ok(win_rates(&mut players, &self.cards, self.games, &mut rng))
+    }
+}
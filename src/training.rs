@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Card::*, Move, Turn};
+
+pub mod cloning;
+pub mod evolution;
+pub mod neural;
+pub mod q_learning;
+
+/// the abstract moves the learned players choose from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MoveAction {
+    /// takes everything and rolls again
+    TakeAll,
+    /// takes everything and writes
+    Write,
+    /// takes only the take with the most points per dice and rolls again
+    TakeOne,
+}
+
+impl MoveAction {
+    pub const ALL: [MoveAction; 3] = [MoveAction::TakeAll, MoveAction::Write, MoveAction::TakeOne];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// turns the abstract move into a move for the roll
+    /// Write rolls on after a tutto, writing there would give up the tutto
+    pub fn to_move(self, turn: &Turn) -> Move {
+        let all = turn.categorize_roll();
+        match self {
+            MoveAction::TakeAll => Move {
+                takes: all,
+                write: false,
+            },
+            MoveAction::Write => {
+                let dice_left = turn.dice_left_after(&turn.dice_of(&all));
+                Move {
+                    takes: all,
+                    write: dice_left != 0,
+                }
+            }
+            MoveAction::TakeOne => {
+                let best = if turn.card == Flush {
+                    all[0]
+                } else {
+                    *all.iter()
+                        .max_by_key(|take| {
+                            let dice = take.into_taken_dice();
+                            dice.points() / dice.number_of_dice() as i32
+                        })
+                        .unwrap()
+                };
+                Move {
+                    takes: vec![best],
+                    write: false,
+                }
+            }
+        }
+    }
+}
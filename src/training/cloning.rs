@@ -0,0 +1,104 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use super::{q_learning::StateKey, MoveAction};
+use crate::{
+    recording::{Decision, DecisionRecord},
+    Card::*,
+    Move, Turn,
+};
+
+/// how often the imitated player chose each action
+/// the counts of a card decision are ordered as stopping and drawing
+#[derive(Default, Serialize, Deserialize)]
+pub struct ClonedPolicy {
+    moves: HashMap<StateKey, [u32; 3]>,
+    cards: HashMap<StateKey, [u32; 2]>,
+    /// the same counts with the lead ignored, asked when the exact state was never seen
+    coarse_moves: HashMap<StateKey, [u32; 3]>,
+    coarse_cards: HashMap<StateKey, [u32; 2]>,
+}
+
+impl ClonedPolicy {
+    /// counts the decisions of the records
+    pub fn fit(records: &[DecisionRecord]) -> Self {
+        let mut policy = Self::default();
+        for record in records {
+            let (state, turn) = record.position.restore();
+            match &record.decision {
+                Decision::Move(this_move) => {
+                    let key = StateKey::for_move(&state, &turn);
+                    let action = classify(&turn, this_move).index();
+                    policy.moves.entry(key).or_default()[action] += 1;
+                    policy.coarse_moves.entry(coarse(key)).or_default()[action] += 1;
+                }
+                Decision::Card(new_card) => {
+                    let key = StateKey::for_card(&state, &turn);
+                    let action = *new_card as usize;
+                    policy.cards.entry(key).or_default()[action] += 1;
+                    policy.coarse_cards.entry(coarse(key)).or_default()[action] += 1;
+                }
+            }
+        }
+        policy
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(ron::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, ron::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// the most frequent action, None for states never recorded
+    pub fn best_move(&self, key: &StateKey) -> Option<MoveAction> {
+        self.moves
+            .get(key)
+            .or_else(|| self.coarse_moves.get(&coarse(*key)))
+            .map(|counts| MoveAction::ALL[most_frequent(counts)])
+    }
+
+    /// the most frequent decision, None for states never recorded
+    pub fn best_card(&self, key: &StateKey) -> Option<bool> {
+        self.cards
+            .get(key)
+            .or_else(|| self.coarse_cards.get(&coarse(*key)))
+            .map(|counts| most_frequent(counts) == 1)
+    }
+
+    /// the number of distinct states recorded
+    pub fn len(&self) -> usize {
+        self.moves.len() + self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// the abstract action closest to a move
+/// setting aside only part of the offered dice counts as TakeOne
+pub fn classify(turn: &Turn, this_move: &Move) -> MoveAction {
+    let dice_left = turn.dice_left_after(&turn.dice_of(&this_move.takes));
+    if this_move.write && dice_left != 0 && turn.card != Clover {
+        return MoveAction::Write;
+    }
+    let all_left = turn.dice_left_after(&turn.dice_of(&turn.categorize_roll()));
+    if dice_left == all_left {
+        MoveAction::TakeAll
+    } else {
+        MoveAction::TakeOne
+    }
+}
+
+fn coarse(key: StateKey) -> StateKey {
+    StateKey { lead: 0, ..key }
+}
+
+/// the first index wins ties
+fn most_frequent(counts: &[u32]) -> usize {
+    (0..counts.len()).rev().max_by_key(|i| counts[*i]).unwrap()
+}
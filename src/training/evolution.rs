@@ -0,0 +1,248 @@
+use std::path::Path;
+
+use anyhow::{bail, ensure};
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    deck::get_official_cards,
+    players::{Player, ThresholdParams, ThresholdPlayer},
+    tournament::win_rates,
+    Card, MyRng,
+};
+
+/// settings of the genetic algorithm
+#[derive(Debug, Clone, Serialize)]
+pub struct EvolutionConfig {
+    pub population: usize,
+    pub generations: usize,
+    /// games played to estimate the win rate of a member
+    pub games: usize,
+    /// the best members copied unchanged into the next generation
+    pub elite: usize,
+    /// the probability that a gene mutates
+    pub mutation_rate: f64,
+    /// multiplies the gene scales of ThresholdParams
+    pub mutation_scale: f64,
+    pub cards: Vec<Card>,
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self {
+            population: 24,
+            generations: 30,
+            games: 300,
+            elite: 2,
+            mutation_rate: 0.3,
+            mutation_scale: 1.0,
+            cards: get_official_cards(),
+        }
+    }
+}
+
+impl EvolutionConfig {
+    /// rejects settings the algorithm can not run with
+    pub fn check(&self) -> anyhow::Result<()> {
+        ensure!(self.population > 0, "the population can not be empty");
+        ensure!(
+            self.elite <= self.population,
+            "the elite can not be larger than the population"
+        );
+        ensure!(
+            (0.0..=1.0).contains(&self.mutation_rate),
+            "the mutation rate is a probability, got {}",
+            self.mutation_rate
+        );
+        ensure!(
+            self.mutation_scale.is_finite(),
+            "the mutation scale has to be finite"
+        );
+        Ok(())
+    }
+
+    /// identifies the settings a checkpoint was made with
+    /// the generations are left out so a finished run can be continued
+    fn fingerprint(&self, opponents: usize) -> anyhow::Result<String> {
+        let config = ron::to_string(&Self {
+            generations: 0,
+            ..self.clone()
+        })?;
+        Ok(format!("{config} against {opponents} opponents"))
+    }
+}
+
+/// the checkpoint written after every generation
+#[derive(Serialize, Deserialize)]
+pub struct Population {
+    /// the settings the population was evolved with
+    #[serde(default)]
+    pub fingerprint: String,
+    pub generation: usize,
+    pub members: Vec<ThresholdParams>,
+    /// the best member of the last evaluated generation and its win rate
+    pub best: Option<(ThresholdParams, f64)>,
+}
+
+impl Population {
+    /// the default parameters and mutations of them
+    fn initial(config: &EvolutionConfig, fingerprint: String, rng: &mut MyRng) -> Self {
+        let default = ThresholdParams::default().to_genes();
+        let members = (0..config.population)
+            .map(|i| {
+                if i == 0 {
+                    ThresholdParams::from_genes(&default)
+                } else {
+                    ThresholdParams::from_genes(&mutate(&default, 1.0, 3.0, rng))
+                }
+            })
+            .collect();
+        Self {
+            fingerprint,
+            generation: 0,
+            members,
+            best: None,
+        }
+    }
+}
+
+/// evolves ThresholdParams for the win rate against the opponents
+///
+/// the population is checkpointed to `dir/population.ron` after every generation and an existing
+/// checkpoint is resumed if it was made with the same settings and number of opponents.
+/// the best parameters are written to `dir/best.ron`
+pub fn evolve(
+    config: &EvolutionConfig,
+    opponents: &mut [&mut dyn Player],
+    dir: &Path,
+    rng: &mut MyRng,
+) -> anyhow::Result<ThresholdParams> {
+    config.check()?;
+    let fingerprint = config.fingerprint(opponents.len())?;
+    let checkpoint = dir.join("population.ron");
+    let mut population = if checkpoint.exists() {
+        let population: Population = ron::from_str(&std::fs::read_to_string(&checkpoint)?)?;
+        if population.fingerprint != fingerprint {
+            bail!(
+                "{} was made with other settings, use another directory",
+                checkpoint.display()
+            );
+        }
+        population
+    } else {
+        Population::initial(config, fingerprint, rng)
+    };
+
+    while population.generation < config.generations {
+        // every member plays the same seed to make the comparison fairer
+        let seed: u64 = rng.gen();
+        let mut scored: Vec<_> = population
+            .members
+            .into_iter()
+            .map(|params| (fitness(&params, opponents, config, seed), params))
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let mean = scored.iter().map(|(f, _)| f).sum::<f64>() / scored.len() as f64;
+        println!(
+            "generation {}: best {:.3} mean {:.3}",
+            population.generation, scored[0].0, mean
+        );
+
+        population = Population {
+            fingerprint: population.fingerprint,
+            generation: population.generation + 1,
+            members: next_generation(&scored, config, rng),
+            best: Some((scored[0].1.clone(), scored[0].0)),
+        };
+        std::fs::write(
+            &checkpoint,
+            ron::ser::to_string_pretty(&population, ron::ser::PrettyConfig::default())?,
+        )?;
+    }
+
+    let (best, _) = population
+        .best
+        .ok_or_else(|| anyhow::anyhow!("no generation was evaluated"))?;
+    std::fs::write(
+        dir.join("best.ron"),
+        ron::ser::to_string_pretty(&best, ron::ser::PrettyConfig::default())?,
+    )?;
+    Ok(best)
+}
+
+/// the win rate of the parameters against the opponents
+fn fitness(
+    params: &ThresholdParams,
+    opponents: &mut [&mut dyn Player],
+    config: &EvolutionConfig,
+    seed: u64,
+) -> f64 {
+    let mut candidate = ThresholdPlayer::new(params.clone());
+    let mut players: Vec<&mut dyn Player> = vec![&mut candidate];
+    players.extend(opponents.iter_mut().map(|p| &mut **p as &mut dyn Player));
+    win_rates(
+        &mut players,
+        &config.cards,
+        config.games,
+        &mut MyRng::seed_from_u64(seed),
+    )[0]
+}
+
+/// elitism, tournament selection, uniform crossover and gaussian mutation
+fn next_generation(
+    scored: &[(f64, ThresholdParams)],
+    config: &EvolutionConfig,
+    rng: &mut MyRng,
+) -> Vec<ThresholdParams> {
+    let mut members: Vec<_> = scored
+        .iter()
+        .take(config.elite)
+        .map(|(_, params)| params.clone())
+        .collect();
+    while members.len() < config.population {
+        let a = select(scored, rng).to_genes();
+        let b = select(scored, rng).to_genes();
+        let child: Vec<_> = a
+            .into_iter()
+            .zip(b)
+            .map(|(a, b)| if rng.gen() { a } else { b })
+            .collect();
+        members.push(ThresholdParams::from_genes(&mutate(
+            &child,
+            config.mutation_rate,
+            config.mutation_scale,
+            rng,
+        )));
+    }
+    members
+}
+
+/// the best of three random members
+fn select<'a>(scored: &'a [(f64, ThresholdParams)], rng: &mut MyRng) -> &'a ThresholdParams {
+    (0..3)
+        .map(|_| &scored[rng.gen_range(0..scored.len())])
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, params)| params)
+        .unwrap()
+}
+
+fn mutate(genes: &[f64], rate: f64, scale: f64, rng: &mut MyRng) -> Vec<f64> {
+    genes
+        .iter()
+        .zip(ThresholdParams::gene_scales())
+        .map(|(gene, step)| {
+            if rng.gen_bool(rate) {
+                gene + gaussian(rng) * step * scale
+            } else {
+                *gene
+            }
+        })
+        .collect()
+}
+
+/// standard normal sample by the Box-Muller transform
+pub(crate) fn gaussian(rng: &mut MyRng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
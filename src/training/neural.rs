@@ -0,0 +1,330 @@
+use std::{ops::Range, path::Path};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::MoveAction;
+use crate::{
+    deck::get_official_cards,
+    features::{encode_card, encode_move, NUMBER_OF_FEATURES},
+    players::Player,
+    tournament::MAX_TURNS,
+    Card, GameState, Move, MyRng, Turn,
+};
+
+/// the outputs of the policy head are the three MoveActions followed by stopping and drawing a card
+pub const NUMBER_OF_OUTPUTS: usize = 5;
+
+/// the outputs legal for a decision
+pub fn legal_outputs(card_decision: bool) -> Range<usize> {
+    if card_decision {
+        3..5
+    } else {
+        0..3
+    }
+}
+
+/// a dense layer, the weights are stored row by row
+#[derive(Clone, Serialize, Deserialize)]
+struct Layer {
+    inputs: usize,
+    outputs: usize,
+    weights: Vec<f32>,
+    bias: Vec<f32>,
+}
+
+impl Layer {
+    /// glorot uniform initialization
+    fn new(inputs: usize, outputs: usize, rng: &mut MyRng) -> Self {
+        let bound = (6.0 / (inputs + outputs) as f32).sqrt();
+        Self {
+            inputs,
+            outputs,
+            weights: (0..inputs * outputs)
+                .map(|_| rng.gen_range(-bound..bound))
+                .collect(),
+            bias: vec![0.0; outputs],
+        }
+    }
+
+    fn zeroed(&self) -> Self {
+        Self {
+            inputs: self.inputs,
+            outputs: self.outputs,
+            weights: vec![0.0; self.weights.len()],
+            bias: vec![0.0; self.bias.len()],
+        }
+    }
+
+    fn forward(&self, x: &[f32]) -> Vec<f32> {
+        self.weights
+            .chunks(self.inputs)
+            .zip(&self.bias)
+            .map(|(row, b)| b + row.iter().zip(x).map(|(w, x)| w * x).sum::<f32>())
+            .collect()
+    }
+
+    /// accumulates the gradient of the parameters and returns the gradient of the input
+    fn backward(&self, x: &[f32], d_out: &[f32], grad: &mut Layer) -> Vec<f32> {
+        let mut d_x = vec![0.0; self.inputs];
+        for (o, d) in d_out.iter().enumerate() {
+            grad.bias[o] += d;
+            let row = o * self.inputs;
+            for i in 0..self.inputs {
+                grad.weights[row + i] += d * x[i];
+                d_x[i] += self.weights[row + i] * d;
+            }
+        }
+        d_x
+    }
+
+    fn step(&mut self, grad: &Layer, learning_rate: f32) {
+        for (w, g) in self.weights.iter_mut().zip(&grad.weights) {
+            *w -= learning_rate * g;
+        }
+        for (b, g) in self.bias.iter_mut().zip(&grad.bias) {
+            *b -= learning_rate * g;
+        }
+    }
+}
+
+/// a multilayer perceptron with tanh hidden layers, a policy head and a value head
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Network {
+    hidden: Vec<Layer>,
+    policy: Layer,
+    value: Layer,
+}
+
+impl Network {
+    pub fn new(hidden: &[usize], rng: &mut MyRng) -> Self {
+        let mut layers = Vec::new();
+        let mut inputs = NUMBER_OF_FEATURES;
+        for size in hidden {
+            layers.push(Layer::new(inputs, *size, rng));
+            inputs = *size;
+        }
+        Self {
+            hidden: layers,
+            policy: Layer::new(inputs, NUMBER_OF_OUTPUTS, rng),
+            value: Layer::new(inputs, 1, rng),
+        }
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(ron::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, ron::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// returns the policy logits and the value of the features
+    pub fn forward(&self, features: &[f32]) -> (Vec<f32>, f32) {
+        let activations = self.activations(features);
+        let last = activations.last().unwrap();
+        (self.policy.forward(last), self.value.forward(last)[0])
+    }
+
+    /// the input followed by the output of every hidden layer
+    fn activations(&self, features: &[f32]) -> Vec<Vec<f32>> {
+        let mut activations = vec![features.to_vec()];
+        for layer in &self.hidden {
+            let x = layer
+                .forward(activations.last().unwrap())
+                .into_iter()
+                .map(f32::tanh)
+                .collect();
+            activations.push(x);
+        }
+        activations
+    }
+
+    fn zeroed(&self) -> Self {
+        Self {
+            hidden: self.hidden.iter().map(Layer::zeroed).collect(),
+            policy: self.policy.zeroed(),
+            value: self.value.zeroed(),
+        }
+    }
+
+    /// accumulates the gradient of the loss given its gradient at the outputs
+    fn backward(&self, activations: &[Vec<f32>], d_logits: &[f32], d_value: f32, grad: &mut Self) {
+        let last = activations.last().unwrap();
+        let mut d_x = self.policy.backward(last, d_logits, &mut grad.policy);
+        let d_x_value = self.value.backward(last, &[d_value], &mut grad.value);
+        for (d, d_value) in d_x.iter_mut().zip(d_x_value) {
+            *d += d_value;
+        }
+        for (i, layer) in self.hidden.iter().enumerate().rev() {
+            // derivative of tanh expressed by its output
+            let d_pre: Vec<_> = d_x
+                .iter()
+                .zip(&activations[i + 1])
+                .map(|(d, y)| d * (1.0 - y * y))
+                .collect();
+            d_x = layer.backward(&activations[i], &d_pre, &mut grad.hidden[i]);
+        }
+    }
+
+    fn step(&mut self, grad: &Self, learning_rate: f32) {
+        for (layer, grad) in self.hidden.iter_mut().zip(&grad.hidden) {
+            layer.step(grad, learning_rate);
+        }
+        self.policy.step(&grad.policy, learning_rate);
+        self.value.step(&grad.value, learning_rate);
+    }
+}
+
+/// the probabilities of the legal outputs
+pub fn softmax(logits: &[f32], legal: Range<usize>) -> Vec<f32> {
+    let max = logits[legal.clone()]
+        .iter()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+    let exp: Vec<_> = logits[legal].iter().map(|l| (l - max).exp()).collect();
+    let sum: f32 = exp.iter().sum();
+    exp.into_iter().map(|e| e / sum).collect()
+}
+
+/// settings of the self-play training
+#[derive(Debug, Clone)]
+pub struct NeuralConfig {
+    pub games: usize,
+    pub players: usize,
+    pub hidden: Vec<usize>,
+    pub learning_rate: f32,
+    /// weight of the squared error of the value head
+    pub value_weight: f32,
+    /// weight of the entropy bonus keeping the policy exploring
+    pub entropy_weight: f32,
+    pub cards: Vec<Card>,
+}
+
+impl Default for NeuralConfig {
+    fn default() -> Self {
+        Self {
+            games: 20_000,
+            players: 3,
+            hidden: vec![32, 32],
+            learning_rate: 0.002,
+            value_weight: 0.5,
+            entropy_weight: 0.01,
+            cards: get_official_cards(),
+        }
+    }
+}
+
+/// trains a network by self-play policy gradient with the value head as baseline
+///
+/// every turn is an episode and its reward is the change of the lead over the best opponent
+/// in thousand points
+pub fn train(config: &NeuralConfig, rng: &mut MyRng) -> Network {
+    let mut learner = Learner {
+        config,
+        network: Network::new(&config.hidden, rng),
+        episode: Vec::new(),
+    };
+    let mut total_reward = 0.0;
+    let mut turns = 0;
+    for game in 0..config.games {
+        let mut state = GameState::new(config.players, config.cards.clone(), rng);
+        for _ in 0..MAX_TURNS {
+            let me = state.get_player_idx();
+            let before = state.lead(me);
+            state.next_turn(&mut learner, rng);
+            let reward = (state.lead(me) - before) as f32 / 1000.0;
+            learner.end_turn(reward);
+            total_reward += reward;
+            turns += 1;
+            if state.winner().is_some() {
+                break;
+            }
+        }
+        if (game + 1) % (config.games / 10).max(1) == 0 {
+            println!(
+                "{} games played, mean reward {:.3}",
+                game + 1,
+                total_reward / turns as f32
+            );
+            total_reward = 0.0;
+            turns = 0;
+        }
+    }
+    learner.network
+}
+
+struct Step {
+    features: Vec<f32>,
+    card_decision: bool,
+    action: usize,
+}
+
+/// samples from the policy and remembers its decisions until the end of the turn
+struct Learner<'a> {
+    config: &'a NeuralConfig,
+    network: Network,
+    episode: Vec<Step>,
+}
+
+impl Learner<'_> {
+    /// samples an output and remembers the step
+    fn choose(&mut self, features: Vec<f32>, card_decision: bool, rng: &mut MyRng) -> usize {
+        let legal = legal_outputs(card_decision);
+        let (logits, _) = self.network.forward(&features);
+        let probs = softmax(&logits, legal.clone());
+        let mut sample: f32 = rng.gen();
+        let mut action = legal.end - 1;
+        for (i, p) in probs.iter().enumerate() {
+            if sample < *p {
+                action = legal.start + i;
+                break;
+            }
+            sample -= p;
+        }
+        self.episode.push(Step {
+            features,
+            card_decision,
+            action,
+        });
+        action
+    }
+
+    /// one gradient step on the decisions of the turn
+    fn end_turn(&mut self, reward: f32) {
+        let network = &mut self.network;
+        let mut grad = network.zeroed();
+        for step in std::mem::take(&mut self.episode) {
+            let legal = legal_outputs(step.card_decision);
+            let activations = network.activations(&step.features);
+            let last = activations.last().unwrap();
+            let logits = network.policy.forward(last);
+            let value = network.value.forward(last)[0];
+            let probs = softmax(&logits, legal.clone());
+            let advantage = reward - value;
+            let entropy: f32 = -probs.iter().map(|p| p * p.max(1e-8).ln()).sum::<f32>();
+
+            let mut d_logits = vec![0.0; NUMBER_OF_OUTPUTS];
+            for (i, p) in probs.iter().enumerate() {
+                let chosen = (legal.start + i == step.action) as u8 as f32;
+                d_logits[legal.start + i] = advantage * (p - chosen)
+                    + self.config.entropy_weight * p * (p.max(1e-8).ln() + entropy);
+            }
+            let d_value = 2.0 * self.config.value_weight * (value - reward);
+            network.backward(&activations, &d_logits, d_value, &mut grad);
+        }
+        network.step(&grad, self.config.learning_rate);
+    }
+}
+
+impl Player for Learner<'_> {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        let action = self.choose(encode_move(state, turn), false, rng);
+        MoveAction::ALL[action].to_move(turn)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        self.choose(encode_card(state, last_turn), true, rng) == 4
+    }
+}
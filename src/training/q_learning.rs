@@ -0,0 +1,216 @@
+use std::{collections::HashMap, path::Path};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::MoveAction;
+use crate::{
+    deck::get_official_cards, players::Player, tournament::MAX_TURNS, Card, GameState, Move, MyRng,
+    Turn, NUMBER_OF_DICE,
+};
+
+/// points buckets above this are merged
+const MAX_POINTS_BUCKET: i32 = 30;
+/// lead buckets beyond this are merged
+const MAX_LEAD_BUCKET: i32 = 5;
+
+/// the compact abstraction of a decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StateKey {
+    pub card: Card,
+    /// the dice left after taking everything, all dice for card decisions
+    pub dice_left: u8,
+    /// the points of the turn at risk in hundreds
+    pub points: u8,
+    /// the lead over the best opponent in thousands
+    pub lead: i8,
+}
+
+impl StateKey {
+    /// the key of a move decision, assuming everything offered is taken
+    pub fn for_move(state: &GameState, turn: &Turn) -> Self {
+        let dice = turn.dice_of(&turn.categorize_roll());
+        Self::new(
+            state,
+            turn.card,
+            turn.dice_left_after(&dice),
+            turn.points_after(&dice),
+        )
+    }
+
+    /// the key of the decision to draw a new card
+    pub fn for_card(state: &GameState, last_turn: &Turn) -> Self {
+        Self::new(
+            state,
+            last_turn.card,
+            NUMBER_OF_DICE,
+            last_turn.previous_cards_total,
+        )
+    }
+
+    fn new(state: &GameState, card: Card, dice_left: usize, points: i32) -> Self {
+        Self {
+            card,
+            dice_left: dice_left as u8,
+            points: (points / 100).clamp(0, MAX_POINTS_BUCKET) as u8,
+            lead: (state.lead(state.get_player_idx()) / 1000)
+                .clamp(-MAX_LEAD_BUCKET, MAX_LEAD_BUCKET) as i8,
+        }
+    }
+}
+
+/// the learned action values
+/// the values of a card decision are ordered as stopping and drawing
+#[derive(Default, Serialize, Deserialize)]
+pub struct QTable {
+    moves: HashMap<StateKey, [f64; 3]>,
+    cards: HashMap<StateKey, [f64; 2]>,
+}
+
+impl QTable {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(ron::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, ron::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// returns None for states never visited
+    pub fn best_move(&self, key: &StateKey) -> Option<MoveAction> {
+        self.moves
+            .get(key)
+            .map(|values| MoveAction::ALL[argmax(values)])
+    }
+
+    /// returns None for states never visited
+    pub fn best_card(&self, key: &StateKey) -> Option<bool> {
+        self.cards.get(key).map(|values| argmax(values) == 1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len() + self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn argmax(values: &[f64]) -> usize {
+    (0..values.len())
+        .max_by(|a, b| values[*a].total_cmp(&values[*b]))
+        .unwrap()
+}
+
+/// settings of the self-play training
+#[derive(Debug, Clone)]
+pub struct QConfig {
+    pub games: usize,
+    pub players: usize,
+    /// the learning rate
+    pub alpha: f64,
+    /// the probability of exploring a random action
+    pub epsilon: f64,
+    pub cards: Vec<Card>,
+}
+
+impl Default for QConfig {
+    fn default() -> Self {
+        Self {
+            games: 20_000,
+            players: 3,
+            alpha: 0.05,
+            epsilon: 0.1,
+            cards: get_official_cards(),
+        }
+    }
+}
+
+/// learns a QTable by self-play
+///
+/// every turn is an episode and its reward is the change of the lead over the best opponent
+/// in thousand points
+pub fn train(config: &QConfig, rng: &mut MyRng) -> QTable {
+    let mut learner = QLearner {
+        alpha: config.alpha,
+        epsilon: config.epsilon,
+        table: QTable::default(),
+        episode: Vec::new(),
+    };
+    for game in 0..config.games {
+        let mut state = GameState::new(config.players, config.cards.clone(), rng);
+        for _ in 0..MAX_TURNS {
+            let me = state.get_player_idx();
+            let before = state.lead(me);
+            state.next_turn(&mut learner, rng);
+            learner.end_turn((state.lead(me) - before) as f64 / 1000.0);
+            if state.winner().is_some() {
+                break;
+            }
+        }
+        if (game + 1) % (config.games / 10).max(1) == 0 {
+            println!(
+                "{} games played, {} states seen",
+                game + 1,
+                learner.table.len()
+            );
+        }
+    }
+    learner.table
+}
+
+enum Step {
+    Move(StateKey, usize),
+    Card(StateKey, usize),
+}
+
+/// epsilon greedy player which remembers its decisions until the end of the turn
+struct QLearner {
+    alpha: f64,
+    epsilon: f64,
+    table: QTable,
+    episode: Vec<Step>,
+}
+
+/// a random action with probability epsilon, the best one otherwise
+fn choose(epsilon: f64, values: &[f64], rng: &mut MyRng) -> usize {
+    if rng.gen_bool(epsilon) {
+        rng.gen_range(0..values.len())
+    } else {
+        argmax(values)
+    }
+}
+
+impl QLearner {
+    /// backs the reward up through the decisions of the turn
+    fn end_turn(&mut self, reward: f64) {
+        let mut target = reward;
+        for step in std::mem::take(&mut self.episode).into_iter().rev() {
+            let values: &mut [f64] = match step {
+                Step::Move(key, _) => self.table.moves.get_mut(&key).unwrap(),
+                Step::Card(key, _) => self.table.cards.get_mut(&key).unwrap(),
+            };
+            let (Step::Move(_, action) | Step::Card(_, action)) = step;
+            values[action] += self.alpha * (target - values[action]);
+            target = values[argmax(values)];
+        }
+    }
+}
+
+impl Player for QLearner {
+    fn make_move(&mut self, state: &GameState, turn: &Turn, rng: &mut MyRng) -> Move {
+        let key = StateKey::for_move(state, turn);
+        let action = choose(self.epsilon, self.table.moves.entry(key).or_default(), rng);
+        self.episode.push(Step::Move(key, action));
+        MoveAction::ALL[action].to_move(turn)
+    }
+
+    fn card_strat(&mut self, state: &GameState, last_turn: &Turn, rng: &mut MyRng) -> bool {
+        let key = StateKey::for_card(state, last_turn);
+        let action = choose(self.epsilon, self.table.cards.entry(key).or_default(), rng);
+        self.episode.push(Step::Card(key, action));
+        action == 1
+    }
+}